@@ -4,6 +4,7 @@ import {
 	Connection,
 	Keypair,
 	PublicKey,
+	SYSVAR_INSTRUCTIONS_PUBKEY,
 	SystemProgram,
 	TransactionInstruction,
 } from "@solana/web3.js";
@@ -20,6 +21,54 @@ export const programId = new PublicKey(DATA_PROGRAM_ID);
  */
 export const PDA_SEED = "data_account_metadata";
 
+/**
+ * Size of the Metadata PDA Account
+ */
+export const METADATA_SIZE = 684;
+
+/**
+ * Sizes of the Metadata PDA Accounts of previous data versions, which only hold a prefix of
+ * the current fields until they are migrated via `MigrateMetadata`
+ */
+export const LEGACY_METADATA_SIZES = [38];
+
+/**
+ * Seed used to derive the Config PDA Account of the program
+ */
+export const CONFIG_SEED = "data_program_config";
+
+/**
+ * Seed used to derive the associated Usage PDA Account of an authority
+ */
+export const USAGE_SEED = "data_authority_usage";
+
+/**
+ * Seed used to derive the associated Session PDA Account of a session key
+ */
+export const SESSION_SEED = "data_account_session";
+
+/**
+ * Seed used to derive the associated Delegation PDA Account of an approved program
+ */
+export const DELEGATION_SEED = "data_account_delegation";
+
+/**
+ * Seed an approved program uses to derive the signer PDA it writes with
+ */
+export const DELEGATE_SIGNER_SEED = "data_program_delegate";
+
+/**
+ * Domain that prefixes the message a write authority signs for `RelayedUpdateDataAccount`
+ */
+export const RELAY_DOMAIN = "data_program_relayed_update";
+
+/**
+ * Program ID of the BPF Upgradeable Loader that owns the program data of the Data Program
+ */
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+	"BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
 /**
  * Enumeration of the data states of the Data Account
  *
@@ -55,6 +104,86 @@ export enum DataTypeOption {
 	JSON = 1,
 	IMG = 2,
 	HTML = 3,
+	DIRECTORY = 4,
+	MANIFEST = 5,
+	LOG = 6,
+	RING = 7,
+}
+
+/**
+ * Enumeration of the roles of the Data Account
+ *
+ * @export
+ * @enum {number}
+ */
+export enum RoleOption {
+	WRITE,
+	FINALIZE,
+	CLOSE,
+	MANAGER,
+}
+
+/**
+ * Enumeration of the instructions a session key can be allowed to sign
+ *
+ * @export
+ * @enum {number}
+ */
+export enum SessionInstructionOption {
+	UPDATE,
+	RESERVE,
+	RESIZE,
+	APPEND,
+}
+
+/**
+ * Size of the header that precedes the data of every record of an append-only log
+ */
+export const LOG_RECORD_HEADER_SIZE = 4 + 32 + 8;
+
+/**
+ * Record appended to a Data Account of type `LOG`
+ *
+ * @export
+ * @interface ILogRecord
+ */
+export interface ILogRecord {
+	/** Base58-encoded string that represents the `PublicKey` that appended the record */
+	appender: string;
+
+	/** Slot in which the record was appended */
+	slot: number;
+
+	/** Data of the record */
+	data: Buffer;
+}
+
+/**
+ * Size of the header at the start of the data of a ring buffer
+ */
+export const RING_HEADER_SIZE = 8 + 8 + 8;
+
+/**
+ * Ring buffer stored in a Data Account of type `RING`
+ *
+ * @export
+ * @interface IRingBuffer
+ */
+export interface IRingBuffer {
+	/** Slot the next record is written to */
+	head: number;
+
+	/** Size in bytes of every record */
+	recordSize: number;
+
+	/** Number of records in the ring buffer */
+	count: number;
+
+	/** Number of records the ring buffer can hold */
+	capacity: number;
+
+	/** Records of the ring buffer in chronological order, starting from the oldest */
+	records: Buffer[];
 }
 
 /**
@@ -71,7 +200,7 @@ export interface IDataAccountMeta {
 	/** Status of the data serialization */
 	serializationStatus: SerializationStatusOption;
 
-	/** Base58-encoded string that represents the `PublicKey` of the authority of the Data Account */
+	/** Base58-encoded string that represents the `PublicKey` of the authority of the Data Account or the default `PublicKey` if renounced (the NFT mint if NFT-bound) */
 	authority: string;
 
 	/** `false` if the Data Account is static (fixed size) and `true` if dynamic (can realloc) */
@@ -85,6 +214,101 @@ export interface IDataAccountMeta {
 
 	/** Bump seed used to derive the Metadata PDA Account */
 	bumpSeed: number;
+
+	/** Slot after which anyone can reclaim the Data Account or `0` if it does not expire */
+	expiresAtSlot: number;
+
+	/** Base58-encoded string that represents the `PublicKey` that receives the lamports of the Data Account when it is reclaimed or `null` if it is NFT-bound without a beneficiary */
+	beneficiary: string | null;
+
+	/** Unix timestamp after which the Data Account is finalized or `0` if it is not scheduled */
+	finalizeAt: number;
+
+	/** Slot after which the Data Account is finalized or `0` if it is not scheduled */
+	finalizeAtSlot: number;
+
+	/** Immutable `[start, end)` byte ranges of the Data Account */
+	frozenRanges: [number, number][];
+	/** Base58-encoded string that represents the `PublicKey` allowed to write to the Data Account or `null` if renounced */
+	writeAuthority: string | null;
+
+	/** Base58-encoded string that represents the `PublicKey` allowed to finalize the Data Account or `null` if renounced */
+	finalizeAuthority: string | null;
+
+	/** Base58-encoded string that represents the `PublicKey` allowed to close the Data Account or `null` if renounced */
+	closeAuthority: string | null;
+
+	/** Base58-encoded string that represents the `PublicKey` allowed to update the authority and manage roles or `null` if renounced */
+	authorityManager: string | null;
+
+	/** Base58-encoded strings that represent the `PublicKey`s of the recovery guardians of the Data Account */
+	guardians: string[];
+
+	/** Number of guardians required to initiate a recovery */
+	guardianThreshold: number;
+
+	/** Number of seconds a recovery has to wait before it can be completed */
+	recoveryDelay: number;
+
+	/** New authority and unix timestamp after which the pending recovery can be completed or `null` if there is none */
+	pendingRecovery: { newAuthority: string; recoverAt: number } | null;
+
+	/** Base58-encoded string that represents the `PublicKey` of the arbiter that can freeze the Data Account or `null` if there is none */
+	arbiter: string | null;
+
+	/** `true` if the Data Account is frozen by its arbiter, which blocks updates, transfers and closing */
+	isArbiterFrozen: boolean;
+
+	/** Nonce the next relayed update has to be signed with */
+	relayNonce: number;
+
+	/** Mint and minimum amount (as a decimal string, in base units) of tokens whose holders can update the Data Account or `null` if there is none */
+	tokenGate: { mint: string; minAmount: string } | null;
+
+	/** Base58-encoded string that represents the `PublicKey` of the NFT mint whose holder controls the Data Account or `null` if it is not NFT-bound */
+	nftMint: string | null;
+
+	/** `true` if anyone can append records to the Data Account when it is an append-only log */
+	isPublicLog: boolean;
+
+	/** Lamports an appender pays per record (or per byte of data if `perByte` is set) and the Base58-encoded treasury they are paid to or `null` if appending is free */
+	appendPrice: {
+		price: number;
+		perByte: boolean;
+		treasury: string | null;
+	} | null;
+}
+
+/**
+ * Program-wide settings stored in the Config PDA Account
+ *
+ * @export
+ * @interface IDataProgramConfig
+ */
+export interface IDataProgramConfig {
+	/** `PublicKey` that can update the config */
+	admin: PublicKey;
+
+	/** `true` if Data Account instructions are paused */
+	isPaused: boolean;
+
+	/** Maximum size in bytes of a Data Account, `0` if unlimited */
+	maxAccountSize: number;
+
+	/** Maximum bytes written by a single instruction, `0` if unlimited */
+	maxWriteSize: number;
+
+	/** Lamports charged when a Data Account is initialized */
+	protocolFee: number;
+
+	/** `PublicKey` that the collected fees are withdrawn to */
+	treasury: PublicKey;
+
+	/** Lamports charged per byte a Data Account grows by */
+	storageFeePerByte: number;
+
+	/** Authorities whose Data Accounts are not charged the storage fee */
+	feeExempt: PublicKey[];
 }
 
 const trueFlag = Buffer.from(new Uint8Array([1]));
@@ -110,6 +334,94 @@ export class DataProgram {
 		);
 	};
 
+	/**
+	 * Returns the Config PDA Account of the program.
+	 *
+	 * **NOTE**: This account is optional and found by its address, so the instruction builders
+	 * always pass it to apply the settings of the program.
+	 *
+	 * @return {[PublicKey, number]}
+	 */
+	static getConfigPDA = (): [PublicKey, number] => {
+		return PublicKey.findProgramAddressSync(
+			[Buffer.from(CONFIG_SEED, "ascii")],
+			programId
+		);
+	};
+
+	/**
+	 * Returns the Usage PDA Account that tracks the bytes and Data Accounts allocated by the authority.
+	 *
+	 * @param {PublicKey} authority
+	 * @return {[PublicKey, number]}
+	 */
+	static getUsagePDA = (authority: PublicKey): [PublicKey, number] => {
+		return PublicKey.findProgramAddressSync(
+			[Buffer.from(USAGE_SEED, "ascii"), authority.toBuffer()],
+			programId
+		);
+	};
+
+	/**
+	 * Returns the Session PDA Account of a session key for the given Data Account.
+	 *
+	 * **NOTE**: This account is found by its address when the session key signs in place
+	 * of the write authority, see `withSession`.
+	 *
+	 * @param {PublicKey} dataKey
+	 * @param {PublicKey} sessionKey
+	 * @return {[PublicKey, number]}
+	 */
+	static getSessionPDA = (
+		dataKey: PublicKey,
+		sessionKey: PublicKey
+	): [PublicKey, number] => {
+		return PublicKey.findProgramAddressSync(
+			[
+				Buffer.from(SESSION_SEED, "ascii"),
+				dataKey.toBuffer(),
+				sessionKey.toBuffer(),
+			],
+			programId
+		);
+	};
+
+	/**
+	 * Returns the Delegation PDA Account of an approved program for the given Data Account.
+	 *
+	 * @param {PublicKey} dataKey
+	 * @param {PublicKey} program
+	 * @return {[PublicKey, number]}
+	 */
+	static getDelegationPDA = (
+		dataKey: PublicKey,
+		program: PublicKey
+	): [PublicKey, number] => {
+		return PublicKey.findProgramAddressSync(
+			[
+				Buffer.from(DELEGATION_SEED, "ascii"),
+				dataKey.toBuffer(),
+				program.toBuffer(),
+			],
+			programId
+		);
+	};
+
+	/**
+	 * Returns the delegate signer PDA that an approved program signs with via `invoke_signed`.
+	 *
+	 * **NOTE**: This PDA is derived from the approved program, not from the Data Program.
+	 *
+	 * @param {PublicKey} program
+	 * @return {[PublicKey, number]}
+	 */
+	static getDelegateSignerPDA = (program: PublicKey): [PublicKey, number] => {
+		return PublicKey.findProgramAddressSync(
+			[Buffer.from(DELEGATE_SIGNER_SEED, "ascii")],
+			program
+		);
+	};
+
 	/**
 	 * Creates a new `Keypair` for the Data Account and returns a
 	 * `SystemProgram.createAccount` instruction with the rent exempt
@@ -145,6 +457,7 @@ export class DataProgram {
 	 * Metadata PDA Account.
 	 *
 	 * **NOTE**: This instruction can also be called using a previously created account to treat it as a Data Account.
+	 * The feepayer also pays the protocol fee, if one is set in the program config.
 	 *
 	 * @param {PublicKey} feePayer Feepayer for creation of the Metadata PDA Account and
 	 * optionally the Data Account (if not previously created).
@@ -167,6 +480,8 @@ export class DataProgram {
 		debug?: boolean
 	): TransactionInstruction => {
 		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const [usage] = this.getUsagePDA(authorityPK);
 		const idx0 = Buffer.from(new Uint8Array([0]));
 		const space = new BN(initialSize).toArrayLike(Buffer, "le", 8);
 		const authority = authorityPK.toBuffer();
@@ -192,6 +507,16 @@ export class DataProgram {
 					isSigner: false,
 					isWritable: false,
 				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
 			],
 			programId,
 			data: Buffer.concat([
@@ -219,6 +544,9 @@ export class DataProgram {
 	 * be ignored if the Data Account is static.
 	 * @param {boolean} verifyFlag Set `true` if the new data should be verified to see that it conforms to the data type.
 	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @param {boolean} [allowSparse] Set to `true` if writing past the end of a dynamic Data Account should be allowed
+	 * to leave a gap. **NOTE**: Otherwise such writes fail.
+	 * @param {number} [fillByte] Byte used to fill the gap left by a sparse write. Defaults to `0`.
 	 * @return {TransactionInstruction}
 	 */
 	static updateDataAccount = (
@@ -229,9 +557,13 @@ export class DataProgram {
 		offset: number,
 		reallocDown: boolean,
 		verifyFlag: boolean,
-		debug?: boolean
+		debug?: boolean,
+		allowSparse?: boolean,
+		fillByte?: number
 	): TransactionInstruction => {
 		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const [usage] = this.getUsagePDA(authority);
 		const idx1 = Buffer.from(new Uint8Array([1]));
 		const offsetBuffer = new BN(offset).toArrayLike(Buffer, "le", 8);
 		const dataTypeBuffer = new BN(dataType).toArrayLike(Buffer, "le", 1);
@@ -258,6 +590,16 @@ export class DataProgram {
 					isSigner: false,
 					isWritable: false,
 				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
 			],
 			programId,
 			data: Buffer.concat([
@@ -269,6 +611,8 @@ export class DataProgram {
 				reallocDown ? trueFlag : falseFlag,
 				verifyFlag ? trueFlag : falseFlag,
 				debug ? trueFlag : falseFlag,
+				allowSparse ? trueFlag : falseFlag,
+				Buffer.from(new Uint8Array([fillByte ?? 0])),
 			]),
 		});
 
@@ -294,13 +638,16 @@ export class DataProgram {
 		debug?: boolean
 	): TransactionInstruction => {
 		const [pda] = this.getPDA(dataAccount);
+		const [oldUsage] = this.getUsagePDA(oldAuthority);
+		const [newUsage] = this.getUsagePDA(newAuthority);
+		const [config] = this.getConfigPDA();
 		const idx2 = Buffer.from(new Uint8Array([2]));
 		const updateAuthorityIx = new TransactionInstruction({
 			keys: [
 				{
 					pubkey: oldAuthority,
 					isSigner: true,
-					isWritable: false,
+					isWritable: true,
 				},
 				{
 					pubkey: dataAccount,
@@ -317,6 +664,26 @@ export class DataProgram {
 					isSigner: true,
 					isWritable: false,
 				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: oldUsage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: newUsage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
 			],
 			programId,
 			data: Buffer.concat([idx2, debug ? trueFlag : falseFlag]),
@@ -341,6 +708,7 @@ export class DataProgram {
 		debug?: boolean
 	): TransactionInstruction => {
 		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
 		const idx3 = Buffer.from(new Uint8Array([3]));
 		const finalizeIx = new TransactionInstruction({
 			keys: [
@@ -359,6 +727,11 @@ export class DataProgram {
 					isSigner: false,
 					isWritable: true,
 				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
 			],
 			programId,
 			data: Buffer.concat([idx3, debug ? trueFlag : falseFlag]),
@@ -382,6 +755,8 @@ export class DataProgram {
 		debug?: boolean
 	): TransactionInstruction => {
 		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const [usage] = this.getUsagePDA(authority);
 		const idx4 = Buffer.from(new Uint8Array([4]));
 		const closeIx = new TransactionInstruction({
 			keys: [
@@ -400,6 +775,16 @@ export class DataProgram {
 					isSigner: false,
 					isWritable: true,
 				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
 			],
 			programId,
 			data: Buffer.concat([idx4, debug ? trueFlag : falseFlag]),
@@ -409,63 +794,3043 @@ export class DataProgram {
 	};
 
 	/**
-	 * Returns the parsed metadata from the associated Metadata PDA AccountInfo.
-	 *
-	 * **NOTE**: This function can be called to parse the output of `getAccountInfo()`
-	 * using the Metadata PDA Account.
-	 *
-	 * @param {AccountInfo<Buffer> | null} metadataInfo
-	 * @return {IDataAccountMeta}
-	 */
-	static parseMetadataFromAccountInfo = (
-		metadataInfo: AccountInfo<Buffer> | null
-	): IDataAccountMeta => {
-		const accountMeta = {} as IDataAccountMeta;
-		if (metadataInfo && metadataInfo.data.length > 0) {
-			const metadata = metadataInfo.data;
-			accountMeta.dataStatus = metadata.subarray(0, 1).readUInt8();
-			accountMeta.serializationStatus = metadata.subarray(1, 2).readUInt8();
-			accountMeta.authority = new PublicKey(
-				metadata.subarray(2, 34)
-			).toBase58();
-			accountMeta.isDynamic = metadata.subarray(34, 35).readUInt8()
-				? true
-				: false;
-			accountMeta.dataVersion = new BN(
-				metadata.subarray(35, 36),
-				"le"
-			).toNumber();
-			accountMeta.dataType = new BN(metadata.subarray(36, 37), "le").toNumber();
-			accountMeta.bumpSeed = new BN(metadata.subarray(37, 38), "le").toNumber();
-		}
-
-		return accountMeta;
-	};
-
-	/**
-	 * Returns the parsed metadata from the associated Metadata PDA Account.
+	 * Returns instruction to add an entry pointing at a Data Account to a directory Data Account.
 	 *
-	 * @param {Connection} connection
-	 * @param {PublicKey} dataKey
-	 * @param {Commitment} commitment
-	 * @return {Promise<IDataAccountMeta>}
+	 * @param {PublicKey} authority Authority of the directory Data Account.
+	 * @param {PublicKey} directory Directory Data Account.
+	 * @param {PublicKey} entry Data Account the entry points at.
+	 * @param {string} name Name of the entry.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
 	 */
-	static parseMetadata = async (
-		connection: Connection,
-		dataKey: PublicKey,
-		commitment: Commitment
-	): Promise<IDataAccountMeta> => {
-		const [metaKey] = this.getPDA(dataKey);
-		const metadataAccount = await connection.getAccountInfo(
-			metaKey,
-			commitment
+	static createDirectoryEntry = (
+		authority: PublicKey,
+		directory: PublicKey,
+		entry: PublicKey,
+		name: string,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [directoryPDA] = this.getPDA(directory);
+		const [entryPDA] = this.getPDA(entry);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const nameBuffer = Buffer.from(name, "utf8");
+		const idx5 = Buffer.from(new Uint8Array([5]));
+		const nameLenBuffer = new BN(nameBuffer.length).toArrayLike(
+			Buffer,
+			"le",
+			4
 		);
-		return this.parseMetadataFromAccountInfo(metadataAccount);
-	};
-
-	/**
-	 * Returns the Data Account's data.
-	 *
+		const createEntryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: directory,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: directoryPDA,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: entry,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: entryPDA,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx5,
+				nameLenBuffer,
+				nameBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return createEntryIx;
+	};
+
+	/**
+	 * Returns instruction to rename an entry of a directory Data Account.
+	 *
+	 * @param {PublicKey} authority Authority of the directory Data Account.
+	 * @param {PublicKey} directory Directory Data Account.
+	 * @param {string} name Current name of the entry.
+	 * @param {string} newName New name of the entry.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static renameDirectoryEntry = (
+		authority: PublicKey,
+		directory: PublicKey,
+		name: string,
+		newName: string,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [directoryPDA] = this.getPDA(directory);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const nameBuffer = Buffer.from(name, "utf8");
+		const newNameBuffer = Buffer.from(newName, "utf8");
+		const idx6 = Buffer.from(new Uint8Array([6]));
+		const nameLenBuffer = new BN(nameBuffer.length).toArrayLike(
+			Buffer,
+			"le",
+			4
+		);
+		const newNameLenBuffer = new BN(newNameBuffer.length).toArrayLike(
+			Buffer,
+			"le",
+			4
+		);
+		const renameEntryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: directory,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: directoryPDA,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx6,
+				nameLenBuffer,
+				nameBuffer,
+				newNameLenBuffer,
+				newNameBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return renameEntryIx;
+	};
+
+	/**
+	 * Returns instruction to move an entry from one directory Data Account to another.
+	 *
+	 * @param {PublicKey} authority Authority of both directory Data Accounts.
+	 * @param {PublicKey} source Directory Data Account that holds the entry.
+	 * @param {PublicKey} destination Directory Data Account the entry is moved to.
+	 * @param {string} name Name of the entry.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static moveDirectoryEntry = (
+		authority: PublicKey,
+		source: PublicKey,
+		destination: PublicKey,
+		name: string,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [sourcePDA] = this.getPDA(source);
+		const [destinationPDA] = this.getPDA(destination);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const nameBuffer = Buffer.from(name, "utf8");
+		const idx7 = Buffer.from(new Uint8Array([7]));
+		const nameLenBuffer = new BN(nameBuffer.length).toArrayLike(
+			Buffer,
+			"le",
+			4
+		);
+		const moveEntryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: source,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: sourcePDA,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: destination,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: destinationPDA,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx7,
+				nameLenBuffer,
+				nameBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return moveEntryIx;
+	};
+
+	/**
+	 * Returns instruction to remove an entry from a directory Data Account.
+	 *
+	 * **NOTE**: The Data Account the entry points at is left untouched.
+	 *
+	 * @param {PublicKey} authority Authority of the directory Data Account.
+	 * @param {PublicKey} directory Directory Data Account.
+	 * @param {string} name Name of the entry.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static unlinkDirectoryEntry = (
+		authority: PublicKey,
+		directory: PublicKey,
+		name: string,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [directoryPDA] = this.getPDA(directory);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const nameBuffer = Buffer.from(name, "utf8");
+		const idx8 = Buffer.from(new Uint8Array([8]));
+		const nameLenBuffer = new BN(nameBuffer.length).toArrayLike(
+			Buffer,
+			"le",
+			4
+		);
+		const unlinkEntryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: directory,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: directoryPDA,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx8,
+				nameLenBuffer,
+				nameBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return unlinkEntryIx;
+	};
+
+	/**
+	 * Returns instruction to append a shard Data Account to the end of a manifest Data Account.
+	 *
+	 * @param {PublicKey} authority Authority of the manifest and shard Data Accounts.
+	 * @param {PublicKey} manifest Manifest Data Account.
+	 * @param {PublicKey} shard Shard Data Account to append.
+	 * @param {Buffer} hash SHA-256 hash of the data of the shard.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static appendShard = (
+		authority: PublicKey,
+		manifest: PublicKey,
+		shard: PublicKey,
+		hash: Buffer,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [manifestPDA] = this.getPDA(manifest);
+		const [shardPDA] = this.getPDA(shard);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx9 = Buffer.from(new Uint8Array([9]));
+		const appendShardIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: manifest,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: manifestPDA,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: shard,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: shardPDA,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx9, hash, debug ? trueFlag : falseFlag]),
+		});
+
+		return appendShardIx;
+	};
+
+	/**
+	 * Returns instruction to finalize a manifest Data Account and all of its shards.
+	 *
+	 * **NOTE**: The shards must be given in manifest order.
+	 *
+	 * @param {PublicKey} authority Finalize authority of the manifest and its shards.
+	 * @param {PublicKey} manifest Manifest Data Account.
+	 * @param {PublicKey[]} shards Shard Data Accounts of the manifest.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static finalizeManifest = (
+		authority: PublicKey,
+		manifest: PublicKey,
+		shards: PublicKey[],
+		debug?: boolean
+	): TransactionInstruction => {
+		const [manifestPDA] = this.getPDA(manifest);
+		const [config] = this.getConfigPDA();
+		const idx10 = Buffer.from(new Uint8Array([10]));
+		const finalizeManifestIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: manifest,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: manifestPDA,
+					isSigner: false,
+					isWritable: true,
+				},
+				...shards.flatMap((shard) => [
+					{
+						pubkey: shard,
+						isSigner: false,
+						isWritable: false,
+					},
+					{
+						pubkey: this.getPDA(shard)[0],
+						isSigner: false,
+						isWritable: true,
+					},
+				]),
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx10, debug ? trueFlag : falseFlag]),
+		});
+
+		return finalizeManifestIx;
+	};
+
+	/**
+	 * Returns instruction to grow a Data Account towards the given space by at most 10 KiB.
+	 *
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {number} space Size in bytes the Data Account should grow towards.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static reserveSpace = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		space: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx11 = Buffer.from(new Uint8Array([11]));
+		const spaceBuffer = new BN(space).toArrayLike(Buffer, "le", 8);
+		const reserveSpaceIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx11, spaceBuffer, debug ? trueFlag : falseFlag]),
+		});
+
+		return reserveSpaceIx;
+	};
+
+	/**
+	 * Returns instruction to grow (zero-filling) or truncate a Data Account to the given length.
+	 *
+	 * **NOTE**: The excess lamports of a truncated Data Account are refunded to its authority
+	 * if it signs, and otherwise stay in the Data Account.
+	 *
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {number} len New size in bytes of the Data Account.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static resizeDataAccount = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		len: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx12 = Buffer.from(new Uint8Array([12]));
+		const lenBuffer = new BN(len).toArrayLike(Buffer, "le", 8);
+		const resizeIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx12, lenBuffer, debug ? trueFlag : falseFlag]),
+		});
+
+		return resizeIx;
+	};
+
+	/**
+	 * Returns instruction to switch a Data Account between static and dynamic.
+	 *
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} isDynamic Set `true` if the Data Account should be dynamic and `false` if static.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setDynamic = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		isDynamic: boolean,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx13 = Buffer.from(new Uint8Array([13]));
+		const setDynamicIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx13,
+				isDynamic ? trueFlag : falseFlag,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setDynamicIx;
+	};
+
+	/**
+	 * Returns instruction to withdraw the lamports above the rent exemption of a Data Account
+	 * and its Metadata PDA Account.
+	 *
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} recipient Account the lamports are withdrawn to.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static withdrawExcessLamports = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		recipient: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx14 = Buffer.from(new Uint8Array([14]));
+		const withdrawIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: recipient,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx14, debug ? trueFlag : falseFlag]),
+		});
+
+		return withdrawIx;
+	};
+
+	/**
+	 * Returns instruction to top up a Data Account and its Metadata PDA Account to their rent exemption.
+	 *
+	 * @param {PublicKey} payer Payer of the missing lamports.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static topUpRent = (
+		payer: PublicKey,
+		dataAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx15 = Buffer.from(new Uint8Array([15]));
+		const topUpRentIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: payer,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx15, debug ? trueFlag : falseFlag]),
+		});
+
+		return topUpRentIx;
+	};
+
+	/**
+	 * Returns instruction to create the Config PDA Account of the program.
+	 *
+	 * **NOTE**: This instruction must be signed by the upgrade authority of the program.
+	 *
+	 * @param {PublicKey} upgradeAuthority Upgrade authority of the Data Program.
+	 * @param {PublicKey} admin Admin that can update the config.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static initializeConfig = (
+		upgradeAuthority: PublicKey,
+		admin: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [config] = this.getConfigPDA();
+		const [programData] = PublicKey.findProgramAddressSync(
+			[programId.toBuffer()],
+			BPF_LOADER_UPGRADEABLE_ID
+		);
+		const idx16 = Buffer.from(new Uint8Array([16]));
+		const initializeConfigIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: upgradeAuthority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: programData,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx16,
+				admin.toBuffer(),
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return initializeConfigIx;
+	};
+
+	/**
+	 * Returns instruction to replace the settings stored in the Config PDA Account.
+	 *
+	 * @param {PublicKey} admin Admin of the Data Program.
+	 * @param {IDataProgramConfig} settings New settings of the Data Program.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static updateConfig = (
+		admin: PublicKey,
+		settings: IDataProgramConfig,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [config] = this.getConfigPDA();
+		const idx17 = Buffer.from(new Uint8Array([17]));
+		const maxAccountSizeBuffer = new BN(settings.maxAccountSize).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const maxWriteSizeBuffer = new BN(settings.maxWriteSize).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const protocolFeeBuffer = new BN(settings.protocolFee).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const storageFeeBuffer = new BN(settings.storageFeePerByte).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const feeExemptLenBuffer = new BN(settings.feeExempt.length).toArrayLike(
+			Buffer,
+			"le",
+			4
+		);
+		const updateConfigIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: admin,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx17,
+				settings.admin.toBuffer(),
+				settings.isPaused ? trueFlag : falseFlag,
+				maxAccountSizeBuffer,
+				maxWriteSizeBuffer,
+				protocolFeeBuffer,
+				settings.treasury.toBuffer(),
+				storageFeeBuffer,
+				feeExemptLenBuffer,
+				...settings.feeExempt.map((key) => key.toBuffer()),
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return updateConfigIx;
+	};
+
+	/**
+	 * Returns instruction to withdraw the collected fees from the Config PDA Account to the treasury.
+	 *
+	 * @param {PublicKey} admin Admin of the Data Program.
+	 * @param {PublicKey} treasury Treasury set in the Config PDA Account.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static withdrawProtocolFees = (
+		admin: PublicKey,
+		treasury: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [config] = this.getConfigPDA();
+		const idx18 = Buffer.from(new Uint8Array([18]));
+		const withdrawIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: admin,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: treasury,
+					isSigner: false,
+					isWritable: true,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx18, debug ? trueFlag : falseFlag]),
+		});
+
+		return withdrawIx;
+	};
+
+	/**
+	 * Returns instruction to set the maximum bytes and Data Accounts an authority can allocate.
+	 *
+	 * **NOTE**: A value of `0` means unlimited.
+	 *
+	 * @param {PublicKey} admin Admin of the Data Program.
+	 * @param {PublicKey} authority Authority whose quota is set.
+	 * @param {number} maxBytes Maximum bytes the authority can allocate.
+	 * @param {number} maxAccounts Maximum Data Accounts the authority can allocate.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setUsageQuota = (
+		admin: PublicKey,
+		authority: PublicKey,
+		maxBytes: number,
+		maxAccounts: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [config] = this.getConfigPDA();
+		const [usage] = this.getUsagePDA(authority);
+		const idx19 = Buffer.from(new Uint8Array([19]));
+		const maxBytesBuffer = new BN(maxBytes).toArrayLike(Buffer, "le", 8);
+		const maxAccountsBuffer = new BN(maxAccounts).toArrayLike(Buffer, "le", 8);
+		const setQuotaIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: admin,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: authority,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx19,
+				maxBytesBuffer,
+				maxAccountsBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setQuotaIx;
+	};
+
+	/**
+	 * Returns instruction to set the slot after which a Data Account can be reclaimed by the beneficiary.
+	 *
+	 * **NOTE**: A slot of `0` removes the expiry and the default `PublicKey` makes the authority the beneficiary.
+	 *
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {number} expiresAtSlot Slot the Data Account expires at.
+	 * @param {PublicKey} beneficiary Account the lamports are reclaimed to.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setExpiry = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		expiresAtSlot: number,
+		beneficiary: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx20 = Buffer.from(new Uint8Array([20]));
+		const expiresAtSlotBuffer = new BN(expiresAtSlot).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const setExpiryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx20,
+				expiresAtSlotBuffer,
+				beneficiary.toBuffer(),
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setExpiryIx;
+	};
+
+	/**
+	 * Returns instruction to close an expired Data Account and transfer its lamports to the beneficiary.
+	 *
+	 * **NOTE**: Anyone can send this instruction.
+	 *
+	 * @param {PublicKey} beneficiary Beneficiary of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static reclaimExpired = (
+		beneficiary: PublicKey,
+		dataAccount: PublicKey,
+		authority: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx21 = Buffer.from(new Uint8Array([21]));
+		const reclaimIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: beneficiary,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx21, debug ? trueFlag : falseFlag]),
+		});
+
+		return reclaimIx;
+	};
+
+	/**
+	 * Returns instruction to schedule the finalization of a Data Account.
+	 *
+	 * **NOTE**: A value of `0` leaves the corresponding schedule unset.
+	 *
+	 * @param {PublicKey} authority Finalize authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {number} finalizeAt Unix timestamp the Data Account can be finalized at.
+	 * @param {number} finalizeAtSlot Slot the Data Account can be finalized at.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static scheduleFinalize = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		finalizeAt: number,
+		finalizeAtSlot: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx22 = Buffer.from(new Uint8Array([22]));
+		const finalizeAtBuffer = new BN(finalizeAt).toTwos(64).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const finalizeAtSlotBuffer = new BN(finalizeAtSlot).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const scheduleFinalizeIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx22,
+				finalizeAtBuffer,
+				finalizeAtSlotBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return scheduleFinalizeIx;
+	};
+
+	/**
+	 * Returns instruction to finalize a Data Account whose scheduled finalization has passed.
+	 *
+	 * **NOTE**: Anyone can send this instruction.
+	 *
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static finalizeScheduled = (
+		dataAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx23 = Buffer.from(new Uint8Array([23]));
+		const finalizeScheduledIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx23, debug ? trueFlag : falseFlag]),
+		});
+
+		return finalizeScheduledIx;
+	};
+
+	/**
+	 * Returns instruction to permanently freeze the `[start, end)` byte range of a Data Account.
+	 *
+	 * @param {PublicKey} authority Finalize authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {number} start First byte of the range.
+	 * @param {number} end Byte right after the range.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static freezeRange = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		start: number,
+		end: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx24 = Buffer.from(new Uint8Array([24]));
+		const startBuffer = new BN(start).toArrayLike(Buffer, "le", 8);
+		const endBuffer = new BN(end).toArrayLike(Buffer, "le", 8);
+		const freezeRangeIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx24,
+				startBuffer,
+				endBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return freezeRangeIx;
+	};
+
+	/**
+	 * Returns instruction to hand a role of a Data Account to a new holder.
+	 *
+	 * **NOTE**: This instruction requires both the current and new holder to be signers.
+	 *
+	 * @param {PublicKey} holder Current holder of the role or the authority manager.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} newHolder New holder of the role.
+	 * @param {RoleOption} role Role to transfer.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static transferRole = (
+		holder: PublicKey,
+		dataAccount: PublicKey,
+		newHolder: PublicKey,
+		role: RoleOption,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx25 = Buffer.from(new Uint8Array([25]));
+		const roleBuffer = new BN(role).toArrayLike(Buffer, "le", 1);
+		const transferRoleIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: holder,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: newHolder,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx25, roleBuffer, debug ? trueFlag : falseFlag]),
+		});
+
+		return transferRoleIx;
+	};
+
+	/**
+	 * Returns instruction to permanently renounce a role of a Data Account so that nobody holds it.
+	 *
+	 * @param {PublicKey} holder Current holder of the role or the authority manager.
+	 * @param {PublicKey} dataAccount
+	 * @param {RoleOption} role Role to renounce.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static renounceRole = (
+		holder: PublicKey,
+		dataAccount: PublicKey,
+		role: RoleOption,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx26 = Buffer.from(new Uint8Array([26]));
+		const roleBuffer = new BN(role).toArrayLike(Buffer, "le", 1);
+		const renounceRoleIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: holder,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx26, roleBuffer, debug ? trueFlag : falseFlag]),
+		});
+
+		return renounceRoleIx;
+	};
+
+	/**
+	 * Returns instruction to permanently renounce the authority, every role and the token gate of a Data Account.
+	 *
+	 * @param {PublicKey} authority Authority manager of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static renounceAuthority = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx27 = Buffer.from(new Uint8Array([27]));
+		const renounceAuthorityIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx27, debug ? trueFlag : falseFlag]),
+		});
+
+		return renounceAuthorityIx;
+	};
+
+	/**
+	 * Returns instruction to set the recovery guardians of a Data Account.
+	 *
+	 * **NOTE**: No guardians and a `threshold` of `0` disable recovery.
+	 *
+	 * @param {PublicKey} authority Authority manager of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey[]} guardians Recovery guardians.
+	 * @param {number} threshold Number of guardians required to initiate a recovery.
+	 * @param {number} delay Seconds before a recovery can be completed.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setGuardians = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		guardians: PublicKey[],
+		threshold: number,
+		delay: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx28 = Buffer.from(new Uint8Array([28]));
+		const guardiansLenBuffer = new BN(guardians.length).toArrayLike(
+			Buffer,
+			"le",
+			4
+		);
+		const thresholdBuffer = new BN(threshold).toArrayLike(Buffer, "le", 1);
+		const delayBuffer = new BN(delay).toTwos(64).toArrayLike(Buffer, "le", 8);
+		const setGuardiansIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx28,
+				guardiansLenBuffer,
+				...guardians.map((guardian) => guardian.toBuffer()),
+				thresholdBuffer,
+				delayBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setGuardiansIx;
+	};
+
+	/**
+	 * Returns instruction to start rotating the authority of a Data Account to a new authority.
+	 *
+	 * **NOTE**: This instruction requires a quorum of guardians to be signers.
+	 *
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} newAuthority New authority of the Data Account.
+	 * @param {PublicKey[]} guardians Guardians approving the recovery.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static initiateRecovery = (
+		dataAccount: PublicKey,
+		newAuthority: PublicKey,
+		guardians: PublicKey[],
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx29 = Buffer.from(new Uint8Array([29]));
+		const initiateRecoveryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: newAuthority,
+					isSigner: false,
+					isWritable: false,
+				},
+				...guardians.map((guardian) => ({
+					pubkey: guardian,
+					isSigner: true,
+					isWritable: false,
+				})),
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx29, debug ? trueFlag : falseFlag]),
+		});
+
+		return initiateRecoveryIx;
+	};
+
+	/**
+	 * Returns instruction to cancel the pending recovery of a Data Account.
+	 *
+	 * @param {PublicKey} authority Authority or authority manager of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static cancelRecovery = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx30 = Buffer.from(new Uint8Array([30]));
+		const cancelRecoveryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx30, debug ? trueFlag : falseFlag]),
+		});
+
+		return cancelRecoveryIx;
+	};
+
+	/**
+	 * Returns instruction to complete the pending recovery of a Data Account once its delay has passed.
+	 *
+	 * **NOTE**: Anyone can send this instruction.
+	 *
+	 * @param {PublicKey} payer Payer for the Usage PDA Account of the new authority, if not created yet.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} oldAuthority Current authority of the Data Account.
+	 * @param {PublicKey} newAuthority Pending authority of the Data Account.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static completeRecovery = (
+		payer: PublicKey,
+		dataAccount: PublicKey,
+		oldAuthority: PublicKey,
+		newAuthority: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [oldUsage] = this.getUsagePDA(oldAuthority);
+		const [newUsage] = this.getUsagePDA(newAuthority);
+		const [config] = this.getConfigPDA();
+		const idx31 = Buffer.from(new Uint8Array([31]));
+		const completeRecoveryIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: payer,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: oldUsage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: newUsage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx31, debug ? trueFlag : falseFlag]),
+		});
+
+		return completeRecoveryIx;
+	};
+
+	/**
+	 * Returns instruction to set the arbiter that can freeze and thaw a Data Account.
+	 *
+	 * **NOTE**: The default `PublicKey` removes the arbiter.
+	 *
+	 * @param {PublicKey} authority Authority manager or current arbiter of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} arbiter New arbiter.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setArbiter = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		arbiter: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx32 = Buffer.from(new Uint8Array([32]));
+		const setArbiterIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx32,
+				arbiter.toBuffer(),
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setArbiterIx;
+	};
+
+	/**
+	 * Returns instruction for the arbiter to freeze a Data Account.
+	 *
+	 * @param {PublicKey} arbiter Arbiter of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static freezeDataAccount = (
+		arbiter: PublicKey,
+		dataAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx33 = Buffer.from(new Uint8Array([33]));
+		const freezeIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: arbiter,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx33, debug ? trueFlag : falseFlag]),
+		});
+
+		return freezeIx;
+	};
+
+	/**
+	 * Returns instruction for the arbiter to thaw a frozen Data Account.
+	 *
+	 * @param {PublicKey} arbiter Arbiter of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static thawDataAccount = (
+		arbiter: PublicKey,
+		dataAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx34 = Buffer.from(new Uint8Array([34]));
+		const thawIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: arbiter,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx34, debug ? trueFlag : falseFlag]),
+		});
+
+		return thawIx;
+	};
+
+	/**
+	 * Returns instruction to update the data of a Data Account on behalf of its write authority.
+	 *
+	 * **NOTE**: This instruction must be preceded by an ed25519 program instruction verifying the
+	 * signature of the write authority over the `relayedUpdateMessage`.
+	 *
+	 * @param {PublicKey} relayer Relayer that pays all fees and rent.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {number} dataType Data type to set.
+	 * @param {Buffer} data Data to be written to Data Account.
+	 * @param {number} offset Byte offset to start writing from.
+	 * @param {boolean} reallocDown Set `true` if the Data Account should realloc down to the end of new data being added.
+	 * @param {boolean} verifyFlag Set `true` if the new data should be verified to see that it conforms to the data type.
+	 * @param {number} nonce Nonce of the write authority, which must be its next unused nonce.
+	 * @param {number} expiresAtSlot Slot after which the signed message can no longer be relayed.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @param {boolean} [allowSparse] Set to `true` if writing past the end of a dynamic Data Account should be allowed
+	 * to leave a gap. **NOTE**: Otherwise such writes fail.
+	 * @param {number} [fillByte] Byte used to fill the gap left by a sparse write. Defaults to `0`.
+	 * @return {TransactionInstruction}
+	 */
+	static relayedUpdateDataAccount = (
+		relayer: PublicKey,
+		dataAccount: PublicKey,
+		authority: PublicKey,
+		dataType: number,
+		data: Buffer,
+		offset: number,
+		reallocDown: boolean,
+		verifyFlag: boolean,
+		nonce: number,
+		expiresAtSlot: number,
+		debug?: boolean,
+		allowSparse?: boolean,
+		fillByte?: number
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx35 = Buffer.from(new Uint8Array([35]));
+		const dataTypeBuffer = new BN(dataType).toArrayLike(Buffer, "le", 1);
+		const dataLenBuffer = new BN(data.length).toArrayLike(Buffer, "le", 4);
+		const offsetBuffer = new BN(offset).toArrayLike(Buffer, "le", 8);
+		const nonceBuffer = new BN(nonce).toArrayLike(Buffer, "le", 8);
+		const expiresAtSlotBuffer = new BN(expiresAtSlot).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const relayedIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: relayer,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SYSVAR_INSTRUCTIONS_PUBKEY,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx35,
+				dataTypeBuffer,
+				dataLenBuffer,
+				data,
+				offsetBuffer,
+				reallocDown ? trueFlag : falseFlag,
+				verifyFlag ? trueFlag : falseFlag,
+				allowSparse ? trueFlag : falseFlag,
+				Buffer.from(new Uint8Array([fillByte ?? 0])),
+				nonceBuffer,
+				expiresAtSlotBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return relayedIx;
+	};
+
+	/**
+	 * Returns instruction to register a session key that can sign in place of the write authority.
+	 *
+	 * **NOTE**: A `maxBytes` of `0` means unlimited.
+	 *
+	 * @param {PublicKey} authority Write authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} sessionKey Session key to register.
+	 * @param {number} expiresAtSlot Slot the session expires at.
+	 * @param {number} maxBytes Maximum bytes the session key can write.
+	 * @param {SessionInstructionOption[]} allowedInstructions Instructions the session key can sign.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static createSession = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		sessionKey: PublicKey,
+		expiresAtSlot: number,
+		maxBytes: number,
+		allowedInstructions: SessionInstructionOption[],
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [session] = this.getSessionPDA(dataAccount, sessionKey);
+		const [config] = this.getConfigPDA();
+		const allowed = allowedInstructions.reduce(
+			(mask, option) => mask | (1 << option),
+			0
+		);
+		const idx36 = Buffer.from(new Uint8Array([36]));
+		const expiresAtSlotBuffer = new BN(expiresAtSlot).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		const maxBytesBuffer = new BN(maxBytes).toArrayLike(Buffer, "le", 8);
+		const allowedBuffer = new BN(allowed).toArrayLike(Buffer, "le", 1);
+		const createSessionIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: session,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx36,
+				sessionKey.toBuffer(),
+				expiresAtSlotBuffer,
+				maxBytesBuffer,
+				allowedBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return createSessionIx;
+	};
+
+	/**
+	 * Returns instruction to revoke a session key and close its Session PDA Account.
+	 *
+	 * @param {PublicKey} authority Write authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} sessionKey Session key to revoke.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static revokeSession = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		sessionKey: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [session] = this.getSessionPDA(dataAccount, sessionKey);
+		const [config] = this.getConfigPDA();
+		const idx37 = Buffer.from(new Uint8Array([37]));
+		const revokeSessionIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: session,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx37, debug ? trueFlag : falseFlag]),
+		});
+
+		return revokeSessionIx;
+	};
+
+	/**
+	 * Returns instruction to approve a program to write to a Data Account via `DelegatedUpdateDataAccount`.
+	 *
+	 * **NOTE**: No ranges means the whole Data Account.
+	 *
+	 * @param {PublicKey} authority Write authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} program Program to approve.
+	 * @param {[number, number][]} ranges Up to 4 `[start, end)` byte ranges the program can write to.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static approveDelegate = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		program: PublicKey,
+		ranges: [number, number][],
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [delegation] = this.getDelegationPDA(dataAccount, program);
+		const [config] = this.getConfigPDA();
+		const idx38 = Buffer.from(new Uint8Array([38]));
+		const rangesLenBuffer = new BN(ranges.length).toArrayLike(Buffer, "le", 4);
+		const approveDelegateIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: delegation,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx38,
+				program.toBuffer(),
+				rangesLenBuffer,
+				...ranges.map(([start, end]) =>
+					Buffer.concat([
+						new BN(start).toArrayLike(Buffer, "le", 8),
+						new BN(end).toArrayLike(Buffer, "le", 8),
+					])
+				),
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return approveDelegateIx;
+	};
+
+	/**
+	 * Returns instruction to revoke an approved program and close its Delegation PDA Account.
+	 *
+	 * @param {PublicKey} authority Write authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} program Program to revoke.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static revokeDelegate = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		program: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [delegation] = this.getDelegationPDA(dataAccount, program);
+		const [config] = this.getConfigPDA();
+		const idx39 = Buffer.from(new Uint8Array([39]));
+		const revokeDelegateIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: delegation,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx39, debug ? trueFlag : falseFlag]),
+		});
+
+		return revokeDelegateIx;
+	};
+
+	/**
+	 * Returns instruction for an approved program to update the data of a Data Account.
+	 *
+	 * **NOTE**: The approved program invokes this instruction and signs it with its delegate signer PDA.
+	 *
+	 * @param {PublicKey} program Approved program.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {number} dataType Data type to set.
+	 * @param {Buffer} data Data to be written to Data Account.
+	 * @param {number} offset Byte offset to start writing from.
+	 * @param {boolean} reallocDown Set `true` if the Data Account should realloc down to the end of new data being added.
+	 * @param {boolean} verifyFlag Set `true` if the new data should be verified to see that it conforms to the data type.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @param {boolean} [allowSparse] Set to `true` if writing past the end of a dynamic Data Account should be allowed
+	 * to leave a gap. **NOTE**: Otherwise such writes fail.
+	 * @param {number} [fillByte] Byte used to fill the gap left by a sparse write. Defaults to `0`.
+	 * @return {TransactionInstruction}
+	 */
+	static delegatedUpdateDataAccount = (
+		program: PublicKey,
+		dataAccount: PublicKey,
+		authority: PublicKey,
+		dataType: number,
+		data: Buffer,
+		offset: number,
+		reallocDown: boolean,
+		verifyFlag: boolean,
+		debug?: boolean,
+		allowSparse?: boolean,
+		fillByte?: number
+	): TransactionInstruction => {
+		const [delegate] = this.getDelegateSignerPDA(program);
+		const [pda] = this.getPDA(dataAccount);
+		const [delegation] = this.getDelegationPDA(dataAccount, program);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx40 = Buffer.from(new Uint8Array([40]));
+		const dataTypeBuffer = new BN(dataType).toArrayLike(Buffer, "le", 1);
+		const dataLenBuffer = new BN(data.length).toArrayLike(Buffer, "le", 4);
+		const offsetBuffer = new BN(offset).toArrayLike(Buffer, "le", 8);
+		const delegatedIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: delegate,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: delegation,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx40,
+				dataTypeBuffer,
+				dataLenBuffer,
+				data,
+				offsetBuffer,
+				reallocDown ? trueFlag : falseFlag,
+				verifyFlag ? trueFlag : falseFlag,
+				allowSparse ? trueFlag : falseFlag,
+				Buffer.from(new Uint8Array([fillByte ?? 0])),
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return delegatedIx;
+	};
+
+	/**
+	 * Returns instruction to let holders of a mint overwrite the data of a Data Account in place.
+	 *
+	 * **NOTE**: The default `PublicKey` removes the token gate.
+	 *
+	 * @param {PublicKey} authority Authority manager of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} mint Mint of the token gate.
+	 * @param {number} minAmount Minimum amount of tokens a holder needs.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setTokenGate = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		mint: PublicKey,
+		minAmount: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx41 = Buffer.from(new Uint8Array([41]));
+		const minAmountBuffer = new BN(minAmount).toArrayLike(Buffer, "le", 8);
+		const setTokenGateIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx41,
+				mint.toBuffer(),
+				minAmountBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setTokenGateIx;
+	};
+
+	/**
+	 * Returns instruction to bind a Data Account to an NFT so that its authority follows the holder.
+	 *
+	 * **NOTE**: The authority manager must hold the NFT in `tokenAccount`.
+	 *
+	 * @param {PublicKey} authority Authority manager of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} mint Mint of the NFT.
+	 * @param {PublicKey} tokenAccount Token account of the authority manager that holds the NFT.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static bindToNft = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		mint: PublicKey,
+		tokenAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [oldUsage] = this.getUsagePDA(authority);
+		const [newUsage] = this.getUsagePDA(mint);
+		const [config] = this.getConfigPDA();
+		const idx42 = Buffer.from(new Uint8Array([42]));
+		const bindToNftIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: mint,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: tokenAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: oldUsage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: newUsage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx42, debug ? trueFlag : falseFlag]),
+		});
+
+		return bindToNftIx;
+	};
+
+	/**
+	 * Returns instruction to turn a Data Account into an append-only log.
+	 *
+	 * **NOTE**: Existing bytes can never be modified or deleted again.
+	 *
+	 * @param {PublicKey} authority Finalize authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} publicAppends Set `true` if anyone should be able to append.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setAppendOnly = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		publicAppends: boolean,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx43 = Buffer.from(new Uint8Array([43]));
+		const setAppendOnlyIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx43,
+				publicAppends ? trueFlag : falseFlag,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setAppendOnlyIx;
+	};
+
+	/**
+	 * Returns instruction to append a record to an append-only log.
+	 *
+	 * @param {PublicKey} appender Appender that pays for the record.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {Buffer} data Data of the record.
+	 * @param {PublicKey} [treasury] Treasury of the log, required if it has an append price.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static appendRecord = (
+		appender: PublicKey,
+		dataAccount: PublicKey,
+		authority: PublicKey,
+		data: Buffer,
+		treasury?: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx44 = Buffer.from(new Uint8Array([44]));
+		const dataLenBuffer = new BN(data.length).toArrayLike(Buffer, "le", 4);
+		const appendRecordIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: appender,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				...(treasury
+					? [
+							{
+								pubkey: treasury,
+								isSigner: false,
+								isWritable: true,
+							},
+					  ]
+					: []),
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx44,
+				dataLenBuffer,
+				data,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return appendRecordIx;
+	};
+
+	/**
+	 * Returns instruction to set the lamports an appender pays to append to an append-only log.
+	 *
+	 * **NOTE**: The default `PublicKey` makes the current authority the treasury.
+	 *
+	 * @param {PublicKey} authority Authority manager of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {number} price Lamports per record or per byte.
+	 * @param {boolean} perByte Set `true` if the price is per byte of data.
+	 * @param {PublicKey} treasury Treasury the price is paid to.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static setAppendPrice = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		price: number,
+		perByte: boolean,
+		treasury: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx45 = Buffer.from(new Uint8Array([45]));
+		const priceBuffer = new BN(price).toArrayLike(Buffer, "le", 8);
+		const setAppendPriceIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx45,
+				priceBuffer,
+				perByte ? trueFlag : falseFlag,
+				treasury.toBuffer(),
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return setAppendPriceIx;
+	};
+
+	/**
+	 * Returns instruction to turn a Data Account into a ring buffer, clearing any previous data.
+	 *
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {number} recordSize Size in bytes of every record.
+	 * @param {number} capacity Number of records the ring buffer holds.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static initializeRingBuffer = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		recordSize: number,
+		capacity: number,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [usage] = this.getUsagePDA(authority);
+		const [config] = this.getConfigPDA();
+		const idx46 = Buffer.from(new Uint8Array([46]));
+		const recordSizeBuffer = new BN(recordSize).toArrayLike(Buffer, "le", 8);
+		const capacityBuffer = new BN(capacity).toArrayLike(Buffer, "le", 8);
+		const initializeRingIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: usage,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: true,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx46,
+				recordSizeBuffer,
+				capacityBuffer,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return initializeRingIx;
+	};
+
+	/**
+	 * Returns instruction to write a record to the head of a ring buffer.
+	 *
+	 * @param {PublicKey} authority Authority of the Data Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {Buffer} data Record of exactly `recordSize` bytes.
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static pushRecord = (
+		authority: PublicKey,
+		dataAccount: PublicKey,
+		data: Buffer,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx47 = Buffer.from(new Uint8Array([47]));
+		const dataLenBuffer = new BN(data.length).toArrayLike(Buffer, "le", 4);
+		const pushRecordIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: authority,
+					isSigner: true,
+					isWritable: false,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([
+				idx47,
+				dataLenBuffer,
+				data,
+				debug ? trueFlag : falseFlag,
+			]),
+		});
+
+		return pushRecordIx;
+	};
+
+	/**
+	 * Returns instruction to grow a legacy Metadata PDA Account to the current layout.
+	 *
+	 * **NOTE**: Anyone can migrate a Metadata PDA Account by paying for its rent.
+	 *
+	 * @param {PublicKey} payer Payer of the rent of the grown Metadata PDA Account.
+	 * @param {PublicKey} dataAccount
+	 * @param {boolean} [debug] Set to `true` if the instruction should output debug logs.
+	 * @return {TransactionInstruction}
+	 */
+	static migrateMetadata = (
+		payer: PublicKey,
+		dataAccount: PublicKey,
+		debug?: boolean
+	): TransactionInstruction => {
+		const [pda] = this.getPDA(dataAccount);
+		const [config] = this.getConfigPDA();
+		const idx48 = Buffer.from(new Uint8Array([48]));
+		const migrateIx = new TransactionInstruction({
+			keys: [
+				{
+					pubkey: payer,
+					isSigner: true,
+					isWritable: true,
+				},
+				{
+					pubkey: dataAccount,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: pda,
+					isSigner: false,
+					isWritable: true,
+				},
+				{
+					pubkey: SystemProgram.programId,
+					isSigner: false,
+					isWritable: false,
+				},
+				{
+					pubkey: config,
+					isSigner: false,
+					isWritable: false,
+				},
+			],
+			programId,
+			data: Buffer.concat([idx48, debug ? trueFlag : falseFlag]),
+		});
+
+		return migrateIx;
+	};
+
+	/**
+	 * Returns the message a write authority signs to allow a relayer to submit
+	 * `RelayedUpdateDataAccount` on its behalf.
+	 *
+	 * **NOTE**: The data is committed to by its SHA-256 hash.
+	 *
+	 * @param {PublicKey} dataAccount
+	 * @param {number} dataType Data type to set.
+	 * @param {Buffer} dataHash SHA-256 hash of the data to be written to Data Account.
+	 * @param {number} offset Byte offset to start writing from.
+	 * @param {boolean} reallocDown Set `true` if the Data Account should realloc down to the end of new data being added.
+	 * @param {boolean} verifyFlag Set `true` if the new data should be verified to see that it conforms to the data type.
+	 * @param {number} nonce Nonce of the write authority, which must be its next unused nonce.
+	 * @param {number} expiresAtSlot Slot after which the signed message can no longer be relayed.
+	 * @param {boolean} [allowSparse] Set to `true` if writing past the end of a dynamic Data Account should be allowed
+	 * to leave a gap.
+	 * @param {number} [fillByte] Byte used to fill the gap left by a sparse write. Defaults to `0`.
+	 * @return {Buffer}
+	 */
+	static relayedUpdateMessage = (
+		dataAccount: PublicKey,
+		dataType: number,
+		dataHash: Buffer,
+		offset: number,
+		reallocDown: boolean,
+		verifyFlag: boolean,
+		nonce: number,
+		expiresAtSlot: number,
+		allowSparse?: boolean,
+		fillByte?: number
+	): Buffer => {
+		const dataTypeBuffer = new BN(dataType).toArrayLike(Buffer, "le", 1);
+		const offsetBuffer = new BN(offset).toArrayLike(Buffer, "le", 8);
+		const nonceBuffer = new BN(nonce).toArrayLike(Buffer, "le", 8);
+		const expiresAtSlotBuffer = new BN(expiresAtSlot).toArrayLike(
+			Buffer,
+			"le",
+			8
+		);
+		return Buffer.concat([
+			Buffer.from(RELAY_DOMAIN, "ascii"),
+			dataAccount.toBuffer(),
+			dataTypeBuffer,
+			dataHash,
+			offsetBuffer,
+			reallocDown ? trueFlag : falseFlag,
+			verifyFlag ? trueFlag : falseFlag,
+			allowSparse ? trueFlag : falseFlag,
+			Buffer.from(new Uint8Array([fillByte ?? 0])),
+			nonceBuffer,
+			expiresAtSlotBuffer,
+		]);
+	};
+
+	/**
+	 * Adds the Session PDA Account of a session key to an instruction so that the
+	 * session key can sign it in place of the write authority.
+	 *
+	 * **NOTE**: The Usage PDA Account of the authority is added as well since the
+	 * instruction builders derive it from the signer.
+	 *
+	 * @param {TransactionInstruction} ix Instruction signed by the session key.
+	 * @param {PublicKey} dataAccount
+	 * @param {PublicKey} sessionKey Session key that signs the instruction.
+	 * @param {PublicKey} authority Write authority of the Data Account.
+	 * @return {TransactionInstruction}
+	 */
+	static withSession = (
+		ix: TransactionInstruction,
+		dataAccount: PublicKey,
+		sessionKey: PublicKey,
+		authority: PublicKey
+	): TransactionInstruction => {
+		const [session] = this.getSessionPDA(dataAccount, sessionKey);
+		const [usage] = this.getUsagePDA(authority);
+		ix.keys.push(
+			{
+				pubkey: session,
+				isSigner: false,
+				isWritable: true,
+			},
+			{
+				pubkey: usage,
+				isSigner: false,
+				isWritable: true,
+			}
+		);
+
+		return ix;
+	};
+
+	/**
+	 * Adds the token account of a holder of the token gate to an instruction so
+	 * that the holder can sign it in place of the write authority.
+	 *
+	 * @param {TransactionInstruction} ix Instruction signed by the holder.
+	 * @param {PublicKey} tokenAccount Token account of the holder for the mint of the token gate.
+	 * @return {TransactionInstruction}
+	 */
+	static withTokenAccount = (
+		ix: TransactionInstruction,
+		tokenAccount: PublicKey
+	): TransactionInstruction => {
+		ix.keys.push({
+			pubkey: tokenAccount,
+			isSigner: false,
+			isWritable: false,
+		});
+
+		return ix;
+	};
+
+	/**
+	 * Returns the parsed metadata from the associated Metadata PDA AccountInfo.
+	 *
+	 * **NOTE**: This function can be called to parse the output of `getAccountInfo()`
+	 * using the Metadata PDA Account.
+	 *
+	 * @param {AccountInfo<Buffer> | null} metadataInfo
+	 * @return {IDataAccountMeta}
+	 */
+	static parseMetadataFromAccountInfo = (
+		metadataInfo: AccountInfo<Buffer> | null
+	): IDataAccountMeta => {
+		const accountMeta = {} as IDataAccountMeta;
+		if (metadataInfo && metadataInfo.data.length > 0) {
+			// read the fields missing from a legacy layout as unset
+			const metadata = LEGACY_METADATA_SIZES.includes(metadataInfo.data.length)
+				? Buffer.concat([
+						metadataInfo.data,
+						Buffer.alloc(METADATA_SIZE - metadataInfo.data.length),
+				  ])
+				: metadataInfo.data;
+			accountMeta.dataStatus = metadata.subarray(0, 1).readUInt8();
+			accountMeta.serializationStatus = metadata.subarray(1, 2).readUInt8();
+			accountMeta.authority = new PublicKey(
+				metadata.subarray(2, 34)
+			).toBase58();
+			accountMeta.isDynamic = metadata.subarray(34, 35).readUInt8()
+				? true
+				: false;
+			accountMeta.dataVersion = new BN(
+				metadata.subarray(35, 36),
+				"le"
+			).toNumber();
+			accountMeta.dataType = new BN(metadata.subarray(36, 37), "le").toNumber();
+			accountMeta.bumpSeed = new BN(metadata.subarray(37, 38), "le").toNumber();
+			accountMeta.expiresAtSlot = new BN(
+				metadata.subarray(38, 46),
+				"le"
+			).toNumber();
+			const isNftBound = metadata.subarray(641, 642).readUInt8() ? true : false;
+			const beneficiary = new PublicKey(metadata.subarray(46, 78));
+			accountMeta.beneficiary = !beneficiary.equals(PublicKey.default)
+				? beneficiary.toBase58()
+				: isNftBound
+				? null
+				: accountMeta.authority;
+			accountMeta.finalizeAt = new BN(metadata.subarray(78, 86), "le")
+				.fromTwos(64)
+				.toNumber();
+			accountMeta.finalizeAtSlot = new BN(
+				metadata.subarray(86, 94),
+				"le"
+			).toNumber();
+			accountMeta.frozenRanges = [];
+			for (let offset = 94; offset < 222; offset += 16) {
+				const start = new BN(metadata.subarray(offset, offset + 8), "le");
+				const end = new BN(metadata.subarray(offset + 8, offset + 16), "le");
+				if (!start.eq(end)) {
+					accountMeta.frozenRanges.push([start.toNumber(), end.toNumber()]);
+				}
+			}
+			const renouncedRoles = metadata.subarray(350, 351).readUInt8();
+			const [writeAuthority, finalizeAuthority, closeAuthority, authorityManager] =
+				[0, 1, 2, 3].map((role) => {
+					if (renouncedRoles & (1 << role)) {
+						return null;
+					}
+					const holder = new PublicKey(
+						metadata.subarray(222 + 32 * role, 254 + 32 * role)
+					);
+					return holder.equals(PublicKey.default)
+						? accountMeta.authority
+						: holder.toBase58();
+				});
+			accountMeta.writeAuthority = writeAuthority;
+			accountMeta.finalizeAuthority = finalizeAuthority;
+			accountMeta.closeAuthority = closeAuthority;
+			accountMeta.authorityManager = authorityManager;
+			accountMeta.guardians = [];
+			for (let offset = 351; offset < 511; offset += 32) {
+				const guardian = new PublicKey(metadata.subarray(offset, offset + 32));
+				if (!guardian.equals(PublicKey.default)) {
+					accountMeta.guardians.push(guardian.toBase58());
+				}
+			}
+			accountMeta.guardianThreshold = metadata.subarray(511, 512).readUInt8();
+			accountMeta.recoveryDelay = new BN(metadata.subarray(512, 520), "le")
+				.fromTwos(64)
+				.toNumber();
+			const recoverAt = new BN(metadata.subarray(552, 560), "le")
+				.fromTwos(64)
+				.toNumber();
+			accountMeta.pendingRecovery = recoverAt
+				? {
+						newAuthority: new PublicKey(metadata.subarray(520, 552)).toBase58(),
+						recoverAt,
+				  }
+				: null;
+			const arbiter = new PublicKey(metadata.subarray(560, 592));
+			accountMeta.arbiter = arbiter.equals(PublicKey.default)
+				? null
+				: arbiter.toBase58();
+			accountMeta.isArbiterFrozen = metadata.subarray(592, 593).readUInt8()
+				? true
+				: false;
+			accountMeta.relayNonce = new BN(
+				metadata.subarray(593, 601),
+				"le"
+			).toNumber();
+			const tokenGateMint = new PublicKey(metadata.subarray(601, 633));
+			accountMeta.tokenGate = tokenGateMint.equals(PublicKey.default)
+				? null
+				: {
+						mint: tokenGateMint.toBase58(),
+						minAmount: new BN(metadata.subarray(633, 641), "le").toString(),
+				  };
+			accountMeta.nftMint = isNftBound ? accountMeta.authority : null;
+			accountMeta.isPublicLog = metadata.subarray(642, 643).readUInt8()
+				? true
+				: false;
+			const appendPrice = new BN(metadata.subarray(643, 651), "le").toNumber();
+			const appendTreasury = new PublicKey(metadata.subarray(652, 684));
+			accountMeta.appendPrice = appendPrice
+				? {
+						price: appendPrice,
+						perByte: metadata.subarray(651, 652).readUInt8() ? true : false,
+						treasury: appendTreasury.equals(PublicKey.default)
+							? null
+							: appendTreasury.toBase58(),
+				  }
+				: null;
+		}
+
+		return accountMeta;
+	};
+
+	/**
+	 * Returns the parsed metadata from the associated Metadata PDA Account.
+	 *
+	 * @param {Connection} connection
+	 * @param {PublicKey} dataKey
+	 * @param {Commitment} commitment
+	 * @return {Promise<IDataAccountMeta>}
+	 */
+	static parseMetadata = async (
+		connection: Connection,
+		dataKey: PublicKey,
+		commitment: Commitment
+	): Promise<IDataAccountMeta> => {
+		const [metaKey] = this.getPDA(dataKey);
+		const metadataAccount = await connection.getAccountInfo(
+			metaKey,
+			commitment
+		);
+		return this.parseMetadataFromAccountInfo(metadataAccount);
+	};
+
+	/**
+	 * Returns the Data Account's data.
+	 *
 	 * @param {Connection} connection
 	 * @param {PublicKey} dataKey
 	 * @param {Commitment} commitment
@@ -479,4 +3844,82 @@ export class DataProgram {
 		const dataAccount = await connection.getAccountInfo(dataKey, commitment);
 		return dataAccount?.data;
 	};
+	/**
+	 * Returns the records of an append-only log in the order they were appended.
+	 *
+	 * **NOTE**: Throws if the data ends in the middle of a record.
+	 *
+	 * @param {Buffer} data
+	 * @return {ILogRecord[]}
+	 */
+	static parseLogRecords = (data: Buffer): ILogRecord[] => {
+		const records: ILogRecord[] = [];
+		let offset = 0;
+		while (offset < data.length) {
+			if (offset + LOG_RECORD_HEADER_SIZE > data.length) {
+				throw new Error("log record header is truncated");
+			}
+			const len = data.readUInt32LE(offset);
+			const start = offset + LOG_RECORD_HEADER_SIZE;
+			if (start + len > data.length) {
+				throw new Error("log record data is truncated");
+			}
+			records.push({
+				appender: new PublicKey(
+					data.subarray(offset + 4, offset + 36)
+				).toBase58(),
+				slot: new BN(data.subarray(offset + 36, start), "le").toNumber(),
+				data: data.subarray(start, start + len),
+			});
+			offset = start + len;
+		}
+		return records;
+	};
+	/**
+	 * Returns the header and the records of a ring buffer in chronological order.
+	 *
+	 * **NOTE**: Throws if the header does not fit the data.
+	 *
+	 * @param {Buffer} data
+	 * @return {IRingBuffer}
+	 */
+	static parseRingBuffer = (data: Buffer): IRingBuffer => {
+		if (data.length < RING_HEADER_SIZE) {
+			throw new Error("ring buffer header is truncated");
+		}
+		const head = new BN(data.subarray(0, 8), "le").toNumber();
+		const recordSize = new BN(data.subarray(8, 16), "le").toNumber();
+		const count = new BN(data.subarray(16, 24), "le").toNumber();
+		const capacity = recordSize
+			? Math.floor((data.length - RING_HEADER_SIZE) / recordSize)
+			: 0;
+		if (capacity === 0 || head >= capacity || count > capacity) {
+			throw new Error("ring buffer header does not fit the data");
+		}
+
+		const records: Buffer[] = [];
+		for (let i = 0; i < count; i++) {
+			const slot = (head + capacity - count + i) % capacity;
+			const offset = RING_HEADER_SIZE + slot * recordSize;
+			records.push(data.subarray(offset, offset + recordSize));
+		}
+		return { head, recordSize, count, capacity, records };
+	};
+
+	/**
+	 * Returns the records of the ring buffer stored in the Data Account in chronological order.
+	 *
+	 * @param {Connection} connection
+	 * @param {PublicKey} dataKey
+	 * @param {Commitment} commitment
+	 * @return {(Promise<Buffer[] | undefined>)}
+	 */
+	static parseRingRecords = async (
+		connection: Connection,
+		dataKey: PublicKey,
+		commitment: Commitment
+	): Promise<Buffer[] | undefined> => {
+		const data = await this.parseData(connection, dataKey, commitment);
+		return data && this.parseRingBuffer(data).records;
+	};
 }
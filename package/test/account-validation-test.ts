@@ -0,0 +1,167 @@
+import {
+	ConfirmOptions,
+	Connection,
+	Keypair,
+	SimulateTransactionConfig,
+	SYSVAR_CLOCK_PUBKEY,
+	Transaction,
+	TransactionInstruction,
+	TransactionMessage,
+	VersionedTransaction,
+	sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { assert } from "./util/utils";
+
+const simulateError = async (
+	connection: Connection,
+	feePayer: Keypair,
+	ix: TransactionInstruction,
+	code: number
+) => {
+	const messageV0 = new TransactionMessage({
+		payerKey: feePayer.publicKey,
+		recentBlockhash: (await connection.getLatestBlockhash("finalized"))
+			.blockhash,
+		instructions: [ix],
+	}).compileToV0Message();
+	const simulateTx = new VersionedTransaction(messageV0);
+	const simulateData = await connection.simulateTransaction(simulateTx, {
+		sigVerify: false,
+	} as SimulateTransactionConfig);
+	assert(simulateData.value.err != null, `simulate did not error out`);
+	assert(
+		JSON.stringify(simulateData.value.err).indexOf(`"Custom":${code}`) != -1,
+		`simulate error ("Custom":${code}) was not found`
+	);
+};
+
+const main = async (connection: Connection, feePayer: Keypair) => {
+	console.log("Authority:", feePayer.publicKey.toBase58());
+	const dataAccount = new Keypair();
+	console.log("Data Account:", dataAccount.publicKey.toBase58());
+
+	const [pda] = DataProgram.getPDA(dataAccount.publicKey);
+	const initializeIx = DataProgram.initializeDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey,
+		feePayer.publicKey,
+		false,
+		true,
+		0
+	);
+	const initializeTx = new Transaction();
+	initializeTx.add(initializeIx);
+	console.log("initializing data account and pda");
+	await sendAndConfirmTransaction(
+		connection,
+		initializeTx,
+		[feePayer, dataAccount],
+		{
+			skipPreflight: true,
+			preflightCommitment: "finalized",
+			confirmation: "finalized",
+		} as ConfirmOptions
+	);
+	console.log("PDA:", pda.toBase58());
+
+	const reinitializeIx = DataProgram.initializeDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey,
+		feePayer.publicKey,
+		true,
+		true,
+		0
+	);
+	console.log("simulate reinitializing data account");
+	await simulateError(connection, feePayer, reinitializeIx, 8);
+
+	const wrongPDAIx = DataProgram.initializeDataAccount(
+		feePayer.publicKey,
+		new Keypair().publicKey,
+		feePayer.publicKey,
+		false,
+		true,
+		0
+	);
+	wrongPDAIx.keys[2].pubkey = pda;
+	console.log("simulate initializing data account with spoofed pda");
+	await simulateError(connection, feePayer, wrongPDAIx, 7);
+
+	const spoofedSysProgramIx = DataProgram.updateDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey,
+		DataTypeOption.CUSTOM,
+		Buffer.from("spoofed"),
+		0,
+		false,
+		false
+	);
+	spoofedSysProgramIx.keys[3].pubkey = new Keypair().publicKey;
+	console.log("simulate updating data account with spoofed system program");
+	await simulateError(connection, feePayer, spoofedSysProgramIx, 5);
+
+	const readonlyUsageIx = DataProgram.updateDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey,
+		DataTypeOption.CUSTOM,
+		Buffer.from("spoofed"),
+		0,
+		false,
+		false
+	);
+	readonlyUsageIx.keys[4].isWritable = false;
+	console.log("simulate updating data account with read-only usage pda");
+	await simulateError(connection, feePayer, readonlyUsageIx, 1);
+
+	const spoofedMetadataIx = DataProgram.finalizeDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey
+	);
+	spoofedMetadataIx.keys[2].pubkey = new Keypair().publicKey;
+	console.log("simulate finalizing data account with spoofed pda");
+	await simulateError(connection, feePayer, spoofedMetadataIx, 22);
+
+	const spoofedDataIx = DataProgram.finalizeDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey
+	);
+	spoofedDataIx.keys[1].pubkey = SYSVAR_CLOCK_PUBKEY;
+	console.log("simulate finalizing data account with spoofed data account");
+	await simulateError(connection, feePayer, spoofedDataIx, 22);
+
+	const legacyUpdateIx = DataProgram.updateDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey,
+		DataTypeOption.CUSTOM,
+		Buffer.from("legacy"),
+		0,
+		false,
+		false
+	);
+	legacyUpdateIx.keys.splice(4);
+	const legacyUpdateTx = new Transaction();
+	legacyUpdateTx.add(legacyUpdateIx);
+	console.log("updating data account without usage and config pdas");
+	await sendAndConfirmTransaction(connection, legacyUpdateTx, [feePayer], {
+		skipPreflight: true,
+		preflightCommitment: "finalized",
+		confirmation: "finalized",
+	} as ConfirmOptions);
+
+	const closeIx = DataProgram.closeDataAccount(
+		feePayer.publicKey,
+		dataAccount.publicKey,
+		false
+	);
+	const closeTx = new Transaction();
+	closeTx.add(closeIx);
+	console.log("closing data account and pda account");
+	await sendAndConfirmTransaction(connection, closeTx, [feePayer], {
+		skipPreflight: true,
+		preflightCommitment: "finalized",
+		confirmation: "finalized",
+	} as ConfirmOptions);
+};
+
+export default main;
@@ -0,0 +1,172 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const appender = secondary.publicKey;
+	const data = Buffer.from("paid!");
+
+	console.log("initializing a public log and a data account");
+	const log = await initializeDataAccount(connection, feePayer);
+	const file = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[DataProgram.setAppendOnly(authority, log.publicKey, true)],
+		[feePayer]
+	);
+
+	console.log("simulate pricing a data account that is not a log");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.setAppendPrice(
+				authority,
+				file.publicKey,
+				1000,
+				false,
+				authority
+			),
+		],
+		60
+	);
+	console.log("simulate pricing the log as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.setAppendPrice(
+				appender,
+				log.publicKey,
+				1000,
+				false,
+				appender
+			),
+		],
+		6
+	);
+
+	console.log("pricing each record, paid to the authority by default");
+	await send(
+		connection,
+		[
+			DataProgram.setAppendPrice(
+				authority,
+				log.publicKey,
+				1000,
+				false,
+				PublicKey.default
+			),
+		],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		log.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.appendPrice?.price === 1000 &&
+			!meta.appendPrice.perByte &&
+			meta.appendPrice.treasury === authority.toBase58(),
+		`append price didn't match`
+	);
+	console.log("simulate paying a different treasury");
+	await expectError(
+		connection,
+		appender,
+		[
+			DataProgram.appendRecord(
+				appender,
+				log.publicKey,
+				authority,
+				data,
+				appender
+			),
+		],
+		63
+	);
+
+	console.log("appending a record priced per record");
+	let balance = await connection.getBalance(authority, "confirmed");
+	await send(
+		connection,
+		[
+			DataProgram.appendRecord(
+				appender,
+				log.publicKey,
+				authority,
+				data,
+				authority
+			),
+		],
+		[secondary]
+	);
+	let paid = (await connection.getBalance(authority, "confirmed")) - balance;
+	assert(
+		paid === 1000,
+		`append price didn't match - expected 1000, got ${paid}`
+	);
+
+	console.log("appending a record priced per byte");
+	await send(
+		connection,
+		[DataProgram.setAppendPrice(authority, log.publicKey, 10, true, authority)],
+		[feePayer]
+	);
+	balance = await connection.getBalance(authority, "confirmed");
+	await send(
+		connection,
+		[
+			DataProgram.appendRecord(
+				appender,
+				log.publicKey,
+				authority,
+				data,
+				authority
+			),
+		],
+		[secondary]
+	);
+	paid = (await connection.getBalance(authority, "confirmed")) - balance;
+	assert(
+		paid === 10 * data.length,
+		`append price didn't match - expected ${10 * data.length}, got ${paid}`
+	);
+
+	console.log("appending a record for free");
+	await send(
+		connection,
+		[
+			DataProgram.setAppendPrice(
+				authority,
+				log.publicKey,
+				0,
+				false,
+				PublicKey.default
+			),
+		],
+		[feePayer]
+	);
+	await send(
+		connection,
+		[DataProgram.appendRecord(appender, log.publicKey, authority, data)],
+		[secondary]
+	);
+	const records = DataProgram.parseLogRecords(
+		(await DataProgram.parseData(
+			connection,
+			log.publicKey,
+			"confirmed"
+		)) as Buffer
+	);
+	assert(records.length === 3, `expected 3 records, got ${records.length}`);
+};
+
+export default main;
@@ -0,0 +1,103 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const writeIx = (authority: PublicKey, dataAccount: PublicKey) =>
+	DataProgram.updateDataAccount(
+		authority,
+		dataAccount,
+		DataTypeOption.CUSTOM,
+		Buffer.from("hello"),
+		0,
+		false,
+		false
+	);
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const arbiter = secondary.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("designating an arbiter");
+	await send(
+		connection,
+		[DataProgram.setArbiter(authority, dataAccount.publicKey, arbiter)],
+		[feePayer]
+	);
+	console.log("simulate replacing the arbiter as the authority");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setArbiter(authority, dataAccount.publicKey, authority)],
+		43
+	);
+	console.log("simulate freezing as the authority");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.freezeDataAccount(authority, dataAccount.publicKey)],
+		43
+	);
+
+	console.log("freezing the data account as the arbiter");
+	await send(
+		connection,
+		[DataProgram.freezeDataAccount(arbiter, dataAccount.publicKey)],
+		[secondary]
+	);
+	let meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(meta.isArbiterFrozen, `data account wasn't frozen`);
+	console.log("simulate updating the frozen data account");
+	await expectError(
+		connection,
+		authority,
+		[writeIx(authority, dataAccount.publicKey)],
+		44
+	);
+	console.log("simulate closing the frozen data account");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.closeDataAccount(authority, dataAccount.publicKey)],
+		44
+	);
+	console.log("simulate freezing the frozen data account");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.freezeDataAccount(arbiter, dataAccount.publicKey)],
+		44
+	);
+
+	console.log("thawing the data account as the arbiter");
+	await send(
+		connection,
+		[DataProgram.thawDataAccount(arbiter, dataAccount.publicKey)],
+		[secondary]
+	);
+	meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(!meta.isArbiterFrozen, `data account wasn't thawed`);
+	console.log("updating the thawed data account");
+	await send(
+		connection,
+		[writeIx(authority, dataAccount.publicKey)],
+		[feePayer]
+	);
+};
+
+export default main;
@@ -0,0 +1,97 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { getConfig } from "./util/config";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const writeIx = (authority: PublicKey, dataAccount: PublicKey, data: Buffer) =>
+	DataProgram.updateDataAccount(
+		authority,
+		dataAccount,
+		DataTypeOption.CUSTOM,
+		data,
+		0,
+		false,
+		false
+	);
+
+const main = async (connection: Connection, feePayer: Keypair) => {
+	const authority = feePayer.publicKey;
+	const config = await getConfig(connection);
+
+	console.log("simulate updating config as a different admin");
+	const stranger = new Keypair().publicKey;
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.updateConfig(stranger, { ...config, isPaused: true })],
+		25
+	);
+
+	if (!config.admin.equals(authority)) {
+		console.log("skipping admin-only tests as primary is not the config admin");
+		return;
+	}
+
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	try {
+		console.log("pausing the program");
+		await send(
+			connection,
+			[DataProgram.updateConfig(authority, { ...config, isPaused: true })],
+			[feePayer]
+		);
+		assert((await getConfig(connection)).isPaused, `program wasn't paused`);
+		console.log("simulate updating data account while paused");
+		await expectError(
+			connection,
+			authority,
+			[writeIx(authority, dataAccount.publicKey, Buffer.from("hello"))],
+			26
+		);
+
+		console.log("limiting the write size");
+		await send(
+			connection,
+			[DataProgram.updateConfig(authority, { ...config, maxWriteSize: 4 })],
+			[feePayer]
+		);
+		console.log("simulate writing more than the write size limit");
+		await expectError(
+			connection,
+			authority,
+			[writeIx(authority, dataAccount.publicKey, Buffer.from("hello"))],
+			28
+		);
+
+		console.log("limiting the account size");
+		await send(
+			connection,
+			[DataProgram.updateConfig(authority, { ...config, maxAccountSize: 4 })],
+			[feePayer]
+		);
+		console.log("simulate growing past the account size limit");
+		await expectError(
+			connection,
+			authority,
+			[writeIx(authority, dataAccount.publicKey, Buffer.from("hello"))],
+			27
+		);
+	} finally {
+		console.log("restoring config");
+		await send(
+			connection,
+			[DataProgram.updateConfig(authority, config)],
+			[feePayer]
+		);
+	}
+
+	console.log("updating data account after unpausing");
+	await send(
+		connection,
+		[writeIx(authority, dataAccount.publicKey, Buffer.from("hello"))],
+		[feePayer]
+	);
+};
+
+export default main;
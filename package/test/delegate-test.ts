@@ -0,0 +1,158 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption, programId } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const MAX_DELEGATE_RANGES = 4;
+
+const delegationPDA = (dataAccount: PublicKey, program: PublicKey) =>
+	DataProgram.getDelegationPDA(dataAccount, program)[0];
+
+/**
+ * Returns a `DelegatedUpdateDataAccount` instruction signed by `delegate` in
+ * place of the delegate signer PDA of the program
+ */
+const delegatedUpdateIx = (
+	delegate: PublicKey,
+	dataAccount: PublicKey,
+	authority: PublicKey,
+	program: PublicKey
+) => {
+	const ix = DataProgram.delegatedUpdateDataAccount(
+		program,
+		dataAccount,
+		authority,
+		DataTypeOption.CUSTOM,
+		Buffer.from("hi"),
+		0,
+		false,
+		false
+	);
+	ix.keys[0].pubkey = delegate;
+	return ix;
+};
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const program = new Keypair().publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("simulate approving a delegate as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.approveDelegate(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				program,
+				[[0, 8]]
+			),
+		],
+		6
+	);
+	console.log("simulate approving an empty range");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.approveDelegate(authority, dataAccount.publicKey, program, [
+				[8, 8],
+			]),
+		],
+		53
+	);
+	console.log("simulate approving too many ranges");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.approveDelegate(
+				authority,
+				dataAccount.publicKey,
+				program,
+				Array.from(
+					{ length: MAX_DELEGATE_RANGES + 1 },
+					(_, i): [number, number] => [i, i + 1]
+				)
+			),
+		],
+		53
+	);
+	console.log("simulate a delegated update without an approval");
+	await expectError(
+		connection,
+		authority,
+		[
+			delegatedUpdateIx(
+				DataProgram.getDelegateSignerPDA(program)[0],
+				dataAccount.publicKey,
+				authority,
+				program
+			),
+		],
+		52
+	);
+
+	console.log("approving a delegate program");
+	await send(
+		connection,
+		[
+			DataProgram.approveDelegate(authority, dataAccount.publicKey, program, [
+				[0, 8],
+			]),
+		],
+		[feePayer]
+	);
+	const delegation = await connection.getAccountInfo(
+		delegationPDA(dataAccount.publicKey, program),
+		"confirmed"
+	);
+	assert(
+		delegation?.owner.equals(programId) ?? false,
+		`delegation pda wasn't created`
+	);
+	console.log("simulate a delegated update not signed by the program");
+	await expectError(
+		connection,
+		authority,
+		[
+			delegatedUpdateIx(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				authority,
+				program
+			),
+		],
+		6
+	);
+
+	console.log("revoking the delegate program");
+	await send(
+		connection,
+		[DataProgram.revokeDelegate(authority, dataAccount.publicKey, program)],
+		[feePayer]
+	);
+	assert(
+		(await connection.getAccountInfo(
+			delegationPDA(dataAccount.publicKey, program),
+			"confirmed"
+		)) === null,
+		`delegation pda wasn't closed`
+	);
+	console.log("simulate revoking the revoked delegate program");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.revokeDelegate(authority, dataAccount.publicKey, program)],
+		52
+	);
+};
+
+export default main;
@@ -0,0 +1,230 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const parseDirectory = (data: Buffer) => {
+	const entries: { name: string; account: string; isDirectory: boolean }[] =
+		[];
+	let offset = 4;
+	for (let i = 0; i < data.readUInt32LE(0); i++) {
+		const len = data.readUInt32LE(offset);
+		const name = data.subarray(offset + 4, offset + 4 + len).toString("utf8");
+		offset += 4 + len;
+		const account = new PublicKey(data.subarray(offset, offset + 32));
+		entries.push({
+			name,
+			account: account.toBase58(),
+			isDirectory: data[offset + 32] === 1,
+		});
+		offset += 33;
+	}
+	return entries;
+};
+
+const entriesOf = async (connection: Connection, directory: PublicKey) =>
+	parseDirectory(
+		(await DataProgram.parseData(connection, directory, "confirmed")) as Buffer
+	);
+
+const main = async (connection: Connection, feePayer: Keypair) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing root and sub directories and a file");
+	const root = await initializeDataAccount(connection, feePayer);
+	const sub = await initializeDataAccount(connection, feePayer);
+	const file = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[root, sub].map((directory) =>
+			DataProgram.updateDataAccount(
+				authority,
+				directory.publicKey,
+				DataTypeOption.DIRECTORY,
+				Buffer.from([0, 0, 0, 0]),
+				0,
+				false,
+				false
+			)
+		),
+		[feePayer]
+	);
+	await send(
+		connection,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				file.publicKey,
+				DataTypeOption.JSON,
+				Buffer.from(`{"hello":"world"}`),
+				0,
+				false,
+				true
+			),
+		],
+		[feePayer]
+	);
+
+	console.log("creating entries");
+	await send(
+		connection,
+		[
+			DataProgram.createDirectoryEntry(
+				authority,
+				root.publicKey,
+				file.publicKey,
+				"a.json"
+			),
+			DataProgram.createDirectoryEntry(
+				authority,
+				root.publicKey,
+				sub.publicKey,
+				"sub"
+			),
+		],
+		[feePayer]
+	);
+	let entries = await entriesOf(connection, root.publicKey);
+	assert(entries.length === 2, `expected 2 entries, got ${entries.length}`);
+	assert(
+		entries[0].name === "a.json" &&
+			entries[0].account === file.publicKey.toBase58() &&
+			!entries[0].isDirectory,
+		`file entry didn't match`
+	);
+	assert(
+		entries[1].name === "sub" && entries[1].isDirectory,
+		`directory entry didn't match`
+	);
+
+	console.log("simulate creating a duplicate entry");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.createDirectoryEntry(
+				authority,
+				root.publicKey,
+				file.publicKey,
+				"a.json"
+			),
+		],
+		15
+	);
+	console.log("simulate creating an entry with an invalid name");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.createDirectoryEntry(
+				authority,
+				root.publicKey,
+				file.publicKey,
+				"a/b.json"
+			),
+		],
+		14
+	);
+	console.log("simulate creating an entry in a file");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.createDirectoryEntry(
+				authority,
+				file.publicKey,
+				sub.publicKey,
+				"sub"
+			),
+		],
+		13
+	);
+	console.log("simulate creating an entry as a different authority");
+	const stranger = new Keypair().publicKey;
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.createDirectoryEntry(
+				stranger,
+				root.publicKey,
+				file.publicKey,
+				"b.json"
+			),
+		],
+		6
+	);
+
+	console.log("renaming an entry");
+	await send(
+		connection,
+		[
+			DataProgram.renameDirectoryEntry(
+				authority,
+				root.publicKey,
+				"a.json",
+				"b.json"
+			),
+		],
+		[feePayer]
+	);
+	entries = await entriesOf(connection, root.publicKey);
+	assert(
+		entries.some((entry) => entry.name === "b.json") &&
+			!entries.some((entry) => entry.name === "a.json"),
+		`entry was not renamed`
+	);
+	console.log("simulate renaming a missing entry");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.renameDirectoryEntry(
+				authority,
+				root.publicKey,
+				"a.json",
+				"c.json"
+			),
+		],
+		16
+	);
+
+	console.log("moving an entry to the sub directory");
+	await send(
+		connection,
+		[
+			DataProgram.moveDirectoryEntry(
+				authority,
+				root.publicKey,
+				sub.publicKey,
+				"b.json"
+			),
+		],
+		[feePayer]
+	);
+	entries = await entriesOf(connection, root.publicKey);
+	assert(entries.length === 1, `entry was not moved out of root`);
+	entries = await entriesOf(connection, sub.publicKey);
+	assert(
+		entries.length === 1 && entries[0].account === file.publicKey.toBase58(),
+		`entry was not moved into sub`
+	);
+
+	console.log("unlinking an entry");
+	await send(
+		connection,
+		[DataProgram.unlinkDirectoryEntry(authority, sub.publicKey, "b.json")],
+		[feePayer]
+	);
+	entries = await entriesOf(connection, sub.publicKey);
+	assert(entries.length === 0, `entry was not unlinked`);
+	console.log("simulate unlinking a missing entry");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.unlinkDirectoryEntry(authority, sub.publicKey, "b.json")],
+		16
+	);
+};
+
+export default main;
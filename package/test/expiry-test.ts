@@ -0,0 +1,130 @@
+import { Connection, Keypair } from "@solana/web3.js";
+import { DataProgram } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("simulate migrating up-to-date metadata");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.migrateMetadata(authority, dataAccount.publicKey)],
+		69
+	);
+
+	console.log("simulate setting expiry as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.setExpiry(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				1,
+				secondary.publicKey
+			),
+		],
+		6
+	);
+
+	console.log("setting a future expiry");
+	const slot = await connection.getSlot("confirmed");
+	await send(
+		connection,
+		[
+			DataProgram.setExpiry(
+				authority,
+				dataAccount.publicKey,
+				slot + 10000,
+				secondary.publicKey
+			),
+		],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.expiresAtSlot === slot + 10000 &&
+			meta.beneficiary === secondary.publicKey.toBase58(),
+		`expiry didn't match`
+	);
+	console.log("simulate reclaiming before expiry");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.reclaimExpired(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				authority
+			),
+		],
+		32
+	);
+
+	console.log("expiring the data account");
+	await send(
+		connection,
+		[
+			DataProgram.setExpiry(
+				authority,
+				dataAccount.publicKey,
+				slot,
+				secondary.publicKey
+			),
+		],
+		[feePayer]
+	);
+	console.log("simulate reclaiming to a different beneficiary");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.reclaimExpired(authority, dataAccount.publicKey, authority)],
+		33
+	);
+
+	console.log("reclaiming the expired data account");
+	const balance = await connection.getBalance(secondary.publicKey, "confirmed");
+	const reclaimable =
+		(await connection.getBalance(dataAccount.publicKey, "confirmed")) +
+		(await connection.getBalance(
+			DataProgram.getPDA(dataAccount.publicKey)[0],
+			"confirmed"
+		));
+	await send(
+		connection,
+		[
+			DataProgram.reclaimExpired(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				authority
+			),
+		],
+		[feePayer]
+	);
+	const reclaimed =
+		(await connection.getBalance(secondary.publicKey, "confirmed")) - balance;
+	assert(
+		reclaimed === reclaimable,
+		`reclaimed lamports didn't match - expected ${reclaimable}, got ${reclaimed}`
+	);
+	assert(
+		(await connection.getAccountInfo(dataAccount.publicKey, "confirmed")) ===
+			null,
+		`data account wasn't closed`
+	);
+};
+
+export default main;
@@ -0,0 +1,186 @@
+import { Connection, Keypair } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { getConfig } from "./util/config";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const configBalance = (connection: Connection) =>
+	connection.getBalance(DataProgram.getConfigPDA()[0], "confirmed");
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const config = await getConfig(connection);
+
+	console.log("simulate withdrawing fees as a different admin");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.withdrawProtocolFees(secondary.publicKey, config.treasury)],
+		25
+	);
+
+	if (!config.admin.equals(authority)) {
+		console.log("skipping admin-only tests as primary is not the config admin");
+		return;
+	}
+
+	const storageFeePerByte = 10;
+	const data = Buffer.alloc(100);
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	try {
+		console.log("charging a storage fee");
+		await send(
+			connection,
+			[
+				DataProgram.updateConfig(authority, {
+					...config,
+					protocolFee: 0,
+					treasury: secondary.publicKey,
+					storageFeePerByte,
+					feeExempt: [],
+				}),
+			],
+			[feePayer]
+		);
+		let balance = await configBalance(connection);
+		const signature = await send(
+			connection,
+			[
+				DataProgram.updateDataAccount(
+					authority,
+					dataAccount.publicKey,
+					DataTypeOption.CUSTOM,
+					data,
+					0,
+					false,
+					false
+				),
+			],
+			[feePayer]
+		);
+		let charged = (await configBalance(connection)) - balance;
+		assert(
+			charged === data.length * storageFeePerByte,
+			`storage fee didn't match - expected ${
+				data.length * storageFeePerByte
+			}, got ${charged}`
+		);
+		const tx = await connection.getTransaction(signature, {
+			commitment: "confirmed",
+			maxSupportedTransactionVersion: 0,
+		});
+		assert(
+			!!tx?.meta?.logMessages?.some((log) =>
+				log.includes(
+					`fee collected: payer=${authority.toBase58()} authority=${authority.toBase58()}`
+				)
+			),
+			`storage fee wasn't logged`
+		);
+
+		console.log("exempting the authority from the storage fee");
+		await send(
+			connection,
+			[
+				DataProgram.updateConfig(authority, {
+					...config,
+					protocolFee: 0,
+					treasury: secondary.publicKey,
+					storageFeePerByte,
+					feeExempt: [authority],
+				}),
+			],
+			[feePayer]
+		);
+		balance = await configBalance(connection);
+		await send(
+			connection,
+			[
+				DataProgram.updateDataAccount(
+					authority,
+					dataAccount.publicKey,
+					DataTypeOption.CUSTOM,
+					data,
+					data.length,
+					false,
+					false
+				),
+			],
+			[feePayer]
+		);
+		charged = (await configBalance(connection)) - balance;
+		assert(charged === 0, `exempt authority was charged ${charged}`);
+		console.log("initializing for the exempt authority with a different payer");
+		balance = await configBalance(connection);
+		const exemptAccount = new Keypair();
+		await send(
+			connection,
+			[
+				DataProgram.initializeDataAccount(
+					secondary.publicKey,
+					exemptAccount.publicKey,
+					authority,
+					false,
+					true,
+					data.length
+				),
+			],
+			[secondary, exemptAccount]
+		);
+		charged = (await configBalance(connection)) - balance;
+		assert(charged === 0, `payer of exempt authority was charged ${charged}`);
+		await send(
+			connection,
+			[DataProgram.closeDataAccount(authority, exemptAccount.publicKey)],
+			[feePayer]
+		);
+
+		console.log("simulate withdrawing fees to a different treasury");
+		await expectError(
+			connection,
+			authority,
+			[DataProgram.withdrawProtocolFees(authority, authority)],
+			29
+		);
+
+		console.log("withdrawing fees to the treasury");
+		const configInfo = await connection.getAccountInfo(
+			DataProgram.getConfigPDA()[0],
+			"confirmed"
+		);
+		const fees =
+			(configInfo?.lamports ?? 0) -
+			(await connection.getMinimumBalanceForRentExemption(
+				configInfo?.data.length ?? 0
+			));
+		const treasuryBalance = await connection.getBalance(
+			secondary.publicKey,
+			"confirmed"
+		);
+		await send(
+			connection,
+			[DataProgram.withdrawProtocolFees(authority, secondary.publicKey)],
+			[feePayer]
+		);
+		const withdrawn =
+			(await connection.getBalance(secondary.publicKey, "confirmed")) -
+			treasuryBalance;
+		assert(
+			withdrawn === fees,
+			`withdrawn fees didn't match - expected ${fees}, got ${withdrawn}`
+		);
+	} finally {
+		console.log("restoring config");
+		await send(
+			connection,
+			[DataProgram.updateConfig(authority, config)],
+			[feePayer]
+		);
+	}
+};
+
+export default main;
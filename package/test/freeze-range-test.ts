@@ -0,0 +1,117 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const MAX_FROZEN_RANGES = 8;
+
+const writeIx = (
+	authority: PublicKey,
+	dataAccount: PublicKey,
+	data: Buffer,
+	offset: number
+) =>
+	DataProgram.updateDataAccount(
+		authority,
+		dataAccount,
+		DataTypeOption.CUSTOM,
+		data,
+		offset,
+		false,
+		false
+	);
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[writeIx(authority, dataAccount.publicKey, Buffer.alloc(32), 0)],
+		[feePayer]
+	);
+
+	console.log("freezing the header");
+	await send(
+		connection,
+		[DataProgram.freezeRange(authority, dataAccount.publicKey, 0, 4)],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.frozenRanges.length === 1 &&
+			meta.frozenRanges[0][0] === 0 &&
+			meta.frozenRanges[0][1] === 4,
+		`frozen ranges didn't match`
+	);
+
+	console.log("simulate writing into the frozen header");
+	await expectError(
+		connection,
+		authority,
+		[writeIx(authority, dataAccount.publicKey, Buffer.from("hello"), 2)],
+		37
+	);
+	console.log("simulate truncating into the frozen header");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.resizeDataAccount(authority, dataAccount.publicKey, 2)],
+		37
+	);
+	console.log("writing after the frozen header");
+	await send(
+		connection,
+		[writeIx(authority, dataAccount.publicKey, Buffer.from("hello"), 4)],
+		[feePayer]
+	);
+
+	console.log("simulate freezing an empty range");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.freezeRange(authority, dataAccount.publicKey, 4, 4)],
+		35
+	);
+	console.log("simulate freezing a range past the end of the data");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.freezeRange(authority, dataAccount.publicKey, 4, 64)],
+		35
+	);
+	console.log("simulate freezing a range as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.freezeRange(secondary.publicKey, dataAccount.publicKey, 4, 8)],
+		6
+	);
+
+	console.log("freezing the maximum number of ranges");
+	await send(
+		connection,
+		Array.from({ length: MAX_FROZEN_RANGES - 1 }, (_, i) =>
+			DataProgram.freezeRange(authority, dataAccount.publicKey, 4 + i, 5 + i)
+		),
+		[feePayer]
+	);
+	console.log("simulate freezing one range too many");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.freezeRange(authority, dataAccount.publicKey, 16, 17)],
+		36
+	);
+};
+
+export default main;
@@ -1,7 +1,32 @@
 import { Connection, Keypair } from "@solana/web3.js";
 import bs58 from "bs58";
 import dotenv from "dotenv";
+import accountValidation from "./account-validation-test";
+import appendPrice from "./append-price-test";
+import arbiter from "./arbiter-test";
 import checkFinal from "./check-final-test";
+import config from "./config-test";
+import delegate from "./delegate-test";
+import directory from "./directory-test";
+import expiry from "./expiry-test";
+import fee from "./fee-test";
+import freezeRange from "./freeze-range-test";
+import lamports from "./lamports-test";
+import log from "./log-test";
+import manifest from "./manifest-test";
+import nft from "./nft-test";
+import quota from "./quota-test";
+import recovery from "./recovery-test";
+import relayed from "./relayed-test";
+import renounceAuthority from "./renounce-authority-test";
+import resize from "./resize-test";
+import ring from "./ring-test";
+import roles from "./roles-test";
+import scheduleFinalize from "./schedule-finalize-test";
+import session from "./session-test";
+import space from "./space-test";
+import sparseWrite from "./sparse-write-test";
+import tokenGate from "./token-gate-test";
 import updateAuth from "./update-auth-test";
 import updateData from "./update-data-test";
 import uploadData from "./upload-data-test";
@@ -44,6 +69,56 @@ const main = async () => {
 	await updateAuth(connection, primary, secondary);
 	console.log("=============== Run test: checkFinal ===============");
 	await checkFinal(connection, primary);
+	console.log("=============== Run test: accountValidation ===============");
+	await accountValidation(connection, primary);
+	console.log("=============== Run test: directory ===============");
+	await directory(connection, primary);
+	console.log("=============== Run test: manifest ===============");
+	await manifest(connection, primary);
+	console.log("=============== Run test: space ===============");
+	await space(connection, primary);
+	console.log("=============== Run test: resize ===============");
+	await resize(connection, primary);
+	console.log("=============== Run test: lamports ===============");
+	await lamports(connection, primary, secondary);
+	console.log("=============== Run test: sparseWrite ===============");
+	await sparseWrite(connection, primary);
+	console.log("=============== Run test: config ===============");
+	await config(connection, primary);
+	console.log("=============== Run test: fee ===============");
+	await fee(connection, primary, secondary);
+	console.log("=============== Run test: quota ===============");
+	await quota(connection, primary, secondary);
+	console.log("=============== Run test: expiry ===============");
+	await expiry(connection, primary, secondary);
+	console.log("=============== Run test: scheduleFinalize ===============");
+	await scheduleFinalize(connection, primary, secondary);
+	console.log("=============== Run test: freezeRange ===============");
+	await freezeRange(connection, primary, secondary);
+	console.log("=============== Run test: roles ===============");
+	await roles(connection, primary, secondary);
+	console.log("=============== Run test: renounceAuthority ===============");
+	await renounceAuthority(connection, primary, secondary);
+	console.log("=============== Run test: recovery ===============");
+	await recovery(connection, primary, secondary);
+	console.log("=============== Run test: arbiter ===============");
+	await arbiter(connection, primary, secondary);
+	console.log("=============== Run test: relayed ===============");
+	await relayed(connection, primary, secondary);
+	console.log("=============== Run test: session ===============");
+	await session(connection, primary, secondary);
+	console.log("=============== Run test: delegate ===============");
+	await delegate(connection, primary, secondary);
+	console.log("=============== Run test: tokenGate ===============");
+	await tokenGate(connection, primary, secondary);
+	console.log("=============== Run test: nft ===============");
+	await nft(connection, primary, secondary);
+	console.log("=============== Run test: log ===============");
+	await log(connection, primary, secondary);
+	console.log("=============== Run test: appendPrice ===============");
+	await appendPrice(connection, primary, secondary);
+	console.log("=============== Run test: ring ===============");
+	await ring(connection, primary, secondary);
 	console.log("=============== All tests successful ===============");
 };
 
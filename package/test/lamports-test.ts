@@ -0,0 +1,89 @@
+import { Connection, Keypair, SystemProgram } from "@solana/web3.js";
+import { DataProgram } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const excess = 1_000_000;
+
+	console.log("initializing an over-funded data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[
+			SystemProgram.transfer({
+				fromPubkey: authority,
+				toPubkey: dataAccount.publicKey,
+				lamports: excess,
+			}),
+		],
+		[feePayer]
+	);
+
+	console.log("simulate withdrawing as a different authority");
+	const recipient = new Keypair().publicKey;
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.withdrawExcessLamports(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				recipient
+			),
+		],
+		6
+	);
+
+	console.log("withdrawing excess lamports");
+	await send(
+		connection,
+		[
+			DataProgram.withdrawExcessLamports(
+				authority,
+				dataAccount.publicKey,
+				recipient
+			),
+		],
+		[feePayer]
+	);
+	const withdrawn = await connection.getBalance(recipient, "confirmed");
+	assert(
+		withdrawn === excess,
+		`recipient balance didn't match - expected ${excess}, got ${withdrawn}`
+	);
+
+	console.log("topping up rent as anyone");
+	const balance = await connection.getBalance(
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	await send(
+		connection,
+		[DataProgram.topUpRent(secondary.publicKey, dataAccount.publicKey)],
+		[secondary]
+	);
+	const toppedUp = await connection.getBalance(
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		toppedUp === balance,
+		`rent-exempt account was topped up - expected ${balance}, got ${toppedUp}`
+	);
+
+	console.log("simulate topping up an account not owned by the program");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.topUpRent(authority, recipient)],
+		22
+	);
+};
+
+export default main;
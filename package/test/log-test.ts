@@ -0,0 +1,173 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const recordsOf = async (connection: Connection, dataAccount: PublicKey) =>
+	DataProgram.parseLogRecords(
+		(await DataProgram.parseData(
+			connection,
+			dataAccount,
+			"confirmed"
+		)) as Buffer
+	);
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing data accounts");
+	const log = await initializeDataAccount(connection, feePayer);
+	const file = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				file.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("hello"),
+				0,
+				false,
+				false
+			),
+		],
+		[feePayer]
+	);
+
+	console.log("simulate turning a data account with data into a log");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setAppendOnly(authority, file.publicKey, false)],
+		61
+	);
+	console.log("simulate appending to a data account that is not a log");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.appendRecord(
+				authority,
+				file.publicKey,
+				authority,
+				Buffer.from("a")
+			),
+		],
+		60
+	);
+	console.log("simulate turning a data account into a log as a stranger");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setAppendOnly(secondary.publicKey, log.publicKey, false)],
+		6
+	);
+
+	console.log("turning an empty data account into a private log");
+	await send(
+		connection,
+		[DataProgram.setAppendOnly(authority, log.publicKey, false)],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		log.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.dataType === DataTypeOption.LOG && !meta.isPublicLog,
+		`data account isn't a private log`
+	);
+
+	console.log("appending a record as the authority");
+	await send(
+		connection,
+		[
+			DataProgram.appendRecord(
+				authority,
+				log.publicKey,
+				authority,
+				Buffer.from("first")
+			),
+		],
+		[feePayer]
+	);
+	let records = await recordsOf(connection, log.publicKey);
+	assert(
+		records.length === 1 &&
+			records[0].appender === authority.toBase58() &&
+			records[0].data.equals(Buffer.from("first")),
+		`log records didn't match`
+	);
+	console.log("simulate appending to a private log as a stranger");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.appendRecord(
+				secondary.publicKey,
+				log.publicKey,
+				authority,
+				Buffer.from("second")
+			),
+		],
+		6
+	);
+	console.log("simulate overwriting the log");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				log.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("rewritten"),
+				0,
+				false,
+				false
+			),
+		],
+		59
+	);
+	console.log("simulate closing the log");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.closeDataAccount(authority, log.publicKey)],
+		59
+	);
+
+	console.log("opening the log to public appends");
+	await send(
+		connection,
+		[DataProgram.setAppendOnly(authority, log.publicKey, true)],
+		[feePayer]
+	);
+	console.log("appending a record as anyone");
+	await send(
+		connection,
+		[
+			DataProgram.appendRecord(
+				secondary.publicKey,
+				log.publicKey,
+				authority,
+				Buffer.from("second")
+			),
+		],
+		[secondary]
+	);
+	records = await recordsOf(connection, log.publicKey);
+	assert(
+		records.length === 2 &&
+			records[1].appender === secondary.publicKey.toBase58() &&
+			records[1].data.equals(Buffer.from("second")),
+		`public log records didn't match`
+	);
+};
+
+export default main;
@@ -0,0 +1,205 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { createHash } from "crypto";
+import { DataProgram, DataStatusOption, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const parseManifest = (data: Buffer) => {
+	const shards: { account: string; len: number; hash: string }[] = [];
+	for (let i = 0; i < data.readUInt32LE(0); i++) {
+		const offset = 4 + i * 72;
+		shards.push({
+			account: new PublicKey(data.subarray(offset, offset + 32)).toBase58(),
+			len: new BN(data.subarray(offset + 32, offset + 40), "le").toNumber(),
+			hash: data.subarray(offset + 40, offset + 72).toString("hex"),
+		});
+	}
+	return shards;
+};
+
+const sha256 = (data: Buffer) => createHash("sha256").update(data).digest();
+
+const main = async (connection: Connection, feePayer: Keypair) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing manifests and shards");
+	const manifest = await initializeDataAccount(connection, feePayer);
+	const badManifest = await initializeDataAccount(connection, feePayer);
+	const contents = [Buffer.from("hello "), Buffer.from("world")];
+	const shards: Keypair[] = [];
+	for (const content of contents) {
+		const shard = await initializeDataAccount(connection, feePayer);
+		await send(
+			connection,
+			[
+				DataProgram.updateDataAccount(
+					authority,
+					shard.publicKey,
+					DataTypeOption.CUSTOM,
+					content,
+					0,
+					false,
+					false
+				),
+			],
+			[feePayer]
+		);
+		shards.push(shard);
+	}
+	await send(
+		connection,
+		[manifest, badManifest].map((account) =>
+			DataProgram.updateDataAccount(
+				authority,
+				account.publicKey,
+				DataTypeOption.MANIFEST,
+				Buffer.from([0, 0, 0, 0]),
+				0,
+				false,
+				false
+			)
+		),
+		[feePayer]
+	);
+
+	console.log("appending shards");
+	await send(
+		connection,
+		shards.map((shard, i) =>
+			DataProgram.appendShard(
+				authority,
+				manifest.publicKey,
+				shard.publicKey,
+				sha256(contents[i])
+			)
+		),
+		[feePayer]
+	);
+	const entries = parseManifest(
+		(await DataProgram.parseData(
+			connection,
+			manifest.publicKey,
+			"confirmed"
+		)) as Buffer
+	);
+	assert(entries.length === 2, `expected 2 shards, got ${entries.length}`);
+	entries.forEach((entry, i) => {
+		assert(
+			entry.account === shards[i].publicKey.toBase58() &&
+				entry.len === contents[i].length &&
+				entry.hash === sha256(contents[i]).toString("hex"),
+			`shard ${i} didn't match`
+		);
+	});
+
+	console.log("simulate appending a duplicate shard");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.appendShard(
+				authority,
+				manifest.publicKey,
+				shards[0].publicKey,
+				sha256(contents[0])
+			),
+		],
+		19
+	);
+	console.log("simulate appending the manifest to itself");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.appendShard(
+				authority,
+				manifest.publicKey,
+				manifest.publicKey,
+				sha256(Buffer.from([]))
+			),
+		],
+		19
+	);
+
+	console.log("simulate finalizing shards out of order");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.finalizeManifest(
+				authority,
+				manifest.publicKey,
+				shards.map((shard) => shard.publicKey).reverse()
+			),
+		],
+		20
+	);
+	console.log("simulate finalizing a shard with the wrong hash");
+	await send(
+		connection,
+		[
+			DataProgram.appendShard(
+				authority,
+				badManifest.publicKey,
+				shards[0].publicKey,
+				sha256(contents[1])
+			),
+		],
+		[feePayer]
+	);
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.finalizeManifest(authority, badManifest.publicKey, [
+				shards[0].publicKey,
+			]),
+		],
+		20
+	);
+
+	console.log("finalizing manifest and shards");
+	await send(
+		connection,
+		[
+			DataProgram.finalizeManifest(
+				authority,
+				manifest.publicKey,
+				shards.map((shard) => shard.publicKey)
+			),
+		],
+		[feePayer]
+	);
+	for (const account of [manifest, ...shards]) {
+		const meta = await DataProgram.parseMetadata(
+			connection,
+			account.publicKey,
+			"confirmed"
+		);
+		assert(
+			meta.dataStatus === DataStatusOption.FINALIZED,
+			`data status didn't match - expected ${DataStatusOption.FINALIZED}, got ${meta.dataStatus}`
+		);
+	}
+
+	console.log("simulate updating a finalized shard");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				shards[0].publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("bye"),
+				0,
+				false,
+				false
+			),
+		],
+		10
+	);
+};
+
+export default main;
@@ -0,0 +1,211 @@
+import {
+	AuthorityType,
+	createMint,
+	getOrCreateAssociatedTokenAccount,
+	mintTo,
+	setAuthority,
+	transfer,
+} from "@solana/spl-token";
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import {
+	expectError,
+	initializeDataAccount,
+	send,
+	usage,
+} from "./util/instructions";
+import { assert } from "./util/utils";
+
+/**
+ * Returns an `UpdateDataAccount` instruction signed by a token holder, whose
+ * token account is found by its owner and mint and whose usage is charged to
+ * the mint the Data Account is bound to
+ */
+const holderWriteIx = (
+	holder: PublicKey,
+	dataAccount: PublicKey,
+	mint: PublicKey,
+	tokenAccount: PublicKey
+) => {
+	const ix = DataProgram.withTokenAccount(
+		DataProgram.updateDataAccount(
+			holder,
+			dataAccount,
+			DataTypeOption.CUSTOM,
+			Buffer.from("nft"),
+			0,
+			false,
+			false
+		),
+		tokenAccount
+	);
+	ix.keys.push(usage(mint));
+	return ix;
+};
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("minting an nft and a fungible token");
+	const mint = await createMint(connection, feePayer, authority, null, 0);
+	const fungibleMint = await createMint(
+		connection,
+		feePayer,
+		authority,
+		null,
+		0
+	);
+	const tokenAccount = await getOrCreateAssociatedTokenAccount(
+		connection,
+		feePayer,
+		mint,
+		authority
+	);
+	const fungibleTokenAccount = await getOrCreateAssociatedTokenAccount(
+		connection,
+		feePayer,
+		fungibleMint,
+		authority
+	);
+	await mintTo(connection, feePayer, mint, tokenAccount.address, feePayer, 1);
+	await setAuthority(
+		connection,
+		feePayer,
+		mint,
+		feePayer,
+		AuthorityType.MintTokens,
+		null
+	);
+	await mintTo(
+		connection,
+		feePayer,
+		fungibleMint,
+		fungibleTokenAccount.address,
+		feePayer,
+		1
+	);
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("simulate binding to a mint that can mint more tokens");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.bindToNft(
+				authority,
+				dataAccount.publicKey,
+				fungibleMint,
+				fungibleTokenAccount.address
+			),
+		],
+		58
+	);
+	console.log("simulate binding as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.bindToNft(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				mint,
+				tokenAccount.address
+			),
+		],
+		6
+	);
+
+	console.log("binding the data account to the nft");
+	await send(
+		connection,
+		[
+			DataProgram.bindToNft(
+				authority,
+				dataAccount.publicKey,
+				mint,
+				tokenAccount.address
+			),
+		],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.nftMint === mint.toBase58() && meta.authority === mint.toBase58(),
+		`data account wasn't bound to the nft`
+	);
+	console.log("simulate binding the bound data account again");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.bindToNft(
+				authority,
+				dataAccount.publicKey,
+				mint,
+				tokenAccount.address
+			),
+		],
+		73
+	);
+
+	console.log("transferring the nft");
+	const holderTokenAccount = await getOrCreateAssociatedTokenAccount(
+		connection,
+		feePayer,
+		mint,
+		secondary.publicKey
+	);
+	await transfer(
+		connection,
+		feePayer,
+		tokenAccount.address,
+		holderTokenAccount.address,
+		feePayer,
+		1
+	);
+	console.log("simulate updating as the previous holder");
+	await expectError(
+		connection,
+		authority,
+		[
+			holderWriteIx(
+				authority,
+				dataAccount.publicKey,
+				mint,
+				tokenAccount.address
+			),
+		],
+		57
+	);
+	console.log("updating as the new holder");
+	await send(
+		connection,
+		[
+			holderWriteIx(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				mint,
+				holderTokenAccount.address
+			),
+		],
+		[secondary]
+	);
+	const data = (await DataProgram.parseData(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	)) as Buffer;
+	assert(data.equals(Buffer.from("nft")), `holder update didn't match`);
+};
+
+export default main;
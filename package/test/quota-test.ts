@@ -0,0 +1,156 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { getConfig } from "./util/config";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const getUsage = async (connection: Connection, authority: PublicKey) => {
+	const data = (
+		await connection.getAccountInfo(
+			DataProgram.getUsagePDA(authority)[0],
+			"confirmed"
+		)
+	)?.data;
+	const readU64 = (offset: number) =>
+		data ? new BN(data.subarray(offset, offset + 8), "le").toNumber() : 0;
+	return {
+		bytes: readU64(32),
+		accounts: readU64(40),
+		maxBytes: readU64(48),
+		maxAccounts: readU64(56),
+	};
+};
+
+const writeIx = (authority: PublicKey, dataAccount: PublicKey, data: Buffer) =>
+	DataProgram.updateDataAccount(
+		authority,
+		dataAccount,
+		DataTypeOption.CUSTOM,
+		data,
+		0,
+		false,
+		false
+	);
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("tracking usage of initialize, update and close");
+	let before = await getUsage(connection, authority);
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[writeIx(authority, dataAccount.publicKey, Buffer.alloc(10))],
+		[feePayer]
+	);
+	let after = await getUsage(connection, authority);
+	assert(
+		after.accounts === before.accounts + 1 && after.bytes === before.bytes + 10,
+		`usage didn't match - expected ${before.accounts + 1} accounts and ${
+			before.bytes + 10
+		} bytes, got ${after.accounts} accounts and ${after.bytes} bytes`
+	);
+	await send(
+		connection,
+		[DataProgram.closeDataAccount(authority, dataAccount.publicKey)],
+		[feePayer]
+	);
+	after = await getUsage(connection, authority);
+	assert(
+		after.accounts === before.accounts && after.bytes === before.bytes,
+		`usage wasn't released on close`
+	);
+
+	console.log("simulate setting a quota as a different admin");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setUsageQuota(secondary.publicKey, secondary.publicKey, 0, 0)],
+		25
+	);
+
+	const config = await getConfig(connection);
+	if (!config.admin.equals(authority)) {
+		console.log("skipping admin-only tests as primary is not the config admin");
+		return;
+	}
+
+	before = await getUsage(connection, secondary.publicKey);
+	try {
+		console.log("setting a quota");
+		await send(
+			connection,
+			[
+				DataProgram.setUsageQuota(
+					authority,
+					secondary.publicKey,
+					before.bytes + 10,
+					before.accounts + 1
+				),
+			],
+			[feePayer]
+		);
+		after = await getUsage(connection, secondary.publicKey);
+		assert(
+			after.maxBytes === before.bytes + 10 &&
+				after.maxAccounts === before.accounts + 1,
+			`quota wasn't set`
+		);
+
+		const quotaAccount = await initializeDataAccount(connection, secondary);
+		console.log("simulate growing past the byte quota");
+		await expectError(
+			connection,
+			secondary.publicKey,
+			[writeIx(secondary.publicKey, quotaAccount.publicKey, Buffer.alloc(20))],
+			31
+		);
+		console.log("simulate initializing past the account quota");
+		await expectError(
+			connection,
+			secondary.publicKey,
+			[
+				DataProgram.initializeDataAccount(
+					secondary.publicKey,
+					new Keypair().publicKey,
+					secondary.publicKey,
+					false,
+					true,
+					0
+				),
+			],
+			31
+		);
+		await send(
+			connection,
+			[
+				DataProgram.closeDataAccount(
+					secondary.publicKey,
+					quotaAccount.publicKey
+				),
+			],
+			[secondary]
+		);
+	} finally {
+		console.log("restoring quota");
+		await send(
+			connection,
+			[
+				DataProgram.setUsageQuota(
+					authority,
+					secondary.publicKey,
+					before.maxBytes,
+					before.maxAccounts
+				),
+			],
+			[feePayer]
+		);
+	}
+};
+
+export default main;
@@ -0,0 +1,181 @@
+import { Connection, Keypair } from "@solana/web3.js";
+import { DataProgram } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const guardians = [new Keypair(), new Keypair(), new Keypair()];
+	const guardianKeys = guardians.map((guardian) => guardian.publicKey);
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("simulate setting guardians that can't reach the threshold");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.setGuardians(
+				authority,
+				dataAccount.publicKey,
+				guardianKeys,
+				4,
+				0
+			),
+		],
+		39
+	);
+	console.log("simulate setting guardians as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.setGuardians(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				guardianKeys,
+				2,
+				0
+			),
+		],
+		6
+	);
+
+	console.log("setting 2 of 3 guardians with a delay of an hour");
+	await send(
+		connection,
+		[
+			DataProgram.setGuardians(
+				authority,
+				dataAccount.publicKey,
+				guardianKeys,
+				2,
+				3600
+			),
+		],
+		[feePayer]
+	);
+
+	console.log("simulate initiating recovery without a quorum");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.initiateRecovery(dataAccount.publicKey, secondary.publicKey, [
+				guardianKeys[0],
+			]),
+		],
+		40
+	);
+	console.log("simulate completing recovery when none is pending");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.completeRecovery(
+				authority,
+				dataAccount.publicKey,
+				authority,
+				secondary.publicKey
+			),
+		],
+		41
+	);
+
+	console.log("initiating recovery");
+	await send(
+		connection,
+		[
+			DataProgram.initiateRecovery(
+				dataAccount.publicKey,
+				secondary.publicKey,
+				guardianKeys.slice(0, 2)
+			),
+		],
+		[feePayer, ...guardians.slice(0, 2)]
+	);
+	console.log("simulate completing recovery before the delay");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.completeRecovery(
+				authority,
+				dataAccount.publicKey,
+				authority,
+				secondary.publicKey
+			),
+		],
+		42
+	);
+
+	console.log("cancelling recovery");
+	await send(
+		connection,
+		[DataProgram.cancelRecovery(authority, dataAccount.publicKey)],
+		[feePayer]
+	);
+	console.log("simulate cancelling recovery when none is pending");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.cancelRecovery(authority, dataAccount.publicKey)],
+		41
+	);
+
+	console.log("recovering without a delay");
+	await send(
+		connection,
+		[
+			DataProgram.setGuardians(
+				authority,
+				dataAccount.publicKey,
+				guardianKeys,
+				2,
+				0
+			),
+		],
+		[feePayer]
+	);
+	await send(
+		connection,
+		[
+			DataProgram.initiateRecovery(
+				dataAccount.publicKey,
+				secondary.publicKey,
+				guardianKeys.slice(1)
+			),
+		],
+		[feePayer, ...guardians.slice(1)]
+	);
+	await send(
+		connection,
+		[
+			DataProgram.completeRecovery(
+				authority,
+				dataAccount.publicKey,
+				authority,
+				secondary.publicKey
+			),
+		],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.authority === secondary.publicKey.toBase58(),
+		`authority didn't match - expected ${secondary.publicKey.toBase58()}, got ${
+			meta.authority
+		}`
+	);
+};
+
+export default main;
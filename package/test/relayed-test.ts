@@ -0,0 +1,158 @@
+import {
+	Connection,
+	Ed25519Program,
+	Keypair,
+	PublicKey,
+} from "@solana/web3.js";
+import { createHash } from "crypto";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+interface IRelayedUpdate {
+	data: Buffer;
+	offset: number;
+	nonce: number;
+	expiresAtSlot: number;
+}
+
+const relayedUpdateIx = (
+	relayer: PublicKey,
+	dataAccount: PublicKey,
+	authority: PublicKey,
+	update: IRelayedUpdate
+) =>
+	DataProgram.relayedUpdateDataAccount(
+		relayer,
+		dataAccount,
+		authority,
+		DataTypeOption.CUSTOM,
+		update.data,
+		update.offset,
+		false,
+		false,
+		update.nonce,
+		update.expiresAtSlot
+	);
+
+/**
+ * Returns the ed25519 program instruction that verifies the signature of the
+ * write authority over the `RelayedUpdateMessage`
+ */
+const signIx = (
+	keypair: Keypair,
+	dataAccount: PublicKey,
+	update: IRelayedUpdate
+) =>
+	Ed25519Program.createInstructionWithPrivateKey({
+		privateKey: keypair.secretKey,
+		message: DataProgram.relayedUpdateMessage(
+			dataAccount,
+			DataTypeOption.CUSTOM,
+			createHash("sha256").update(update.data).digest(),
+			update.offset,
+			false,
+			false,
+			update.nonce,
+			update.expiresAtSlot
+		),
+	});
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const relayer = secondary.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	const slot = await connection.getSlot("confirmed");
+	const update: IRelayedUpdate = {
+		data: Buffer.from("relayed"),
+		offset: 0,
+		nonce: 0,
+		expiresAtSlot: slot + 1000,
+	};
+
+	console.log("simulate relaying without a signature");
+	await expectError(
+		connection,
+		relayer,
+		[relayedUpdateIx(relayer, dataAccount.publicKey, authority, update)],
+		45
+	);
+	console.log("simulate relaying data that wasn't signed");
+	await expectError(
+		connection,
+		relayer,
+		[
+			signIx(feePayer, dataAccount.publicKey, update),
+			relayedUpdateIx(relayer, dataAccount.publicKey, authority, {
+				...update,
+				data: Buffer.from("tampered"),
+			}),
+		],
+		45
+	);
+	console.log("simulate relaying an update signed by a different authority");
+	await expectError(
+		connection,
+		relayer,
+		[
+			signIx(secondary, dataAccount.publicKey, update),
+			relayedUpdateIx(relayer, dataAccount.publicKey, authority, update),
+		],
+		6
+	);
+
+	console.log("relaying an update signed by the authority");
+	await send(
+		connection,
+		[
+			signIx(feePayer, dataAccount.publicKey, update),
+			relayedUpdateIx(relayer, dataAccount.publicKey, authority, update),
+		],
+		[secondary]
+	);
+	const data = (await DataProgram.parseData(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	)) as Buffer;
+	assert(data.equals(update.data), `relayed data didn't match`);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.relayNonce === 1,
+		`relay nonce didn't match - expected 1, got ${meta.relayNonce}`
+	);
+
+	console.log("simulate replaying the relayed update");
+	await expectError(
+		connection,
+		relayer,
+		[
+			signIx(feePayer, dataAccount.publicKey, update),
+			relayedUpdateIx(relayer, dataAccount.publicKey, authority, update),
+		],
+		46
+	);
+	console.log("simulate relaying an expired update");
+	const expired = { ...update, nonce: 1, expiresAtSlot: 1 };
+	await expectError(
+		connection,
+		relayer,
+		[
+			signIx(feePayer, dataAccount.publicKey, expired),
+			relayedUpdateIx(relayer, dataAccount.publicKey, authority, expired),
+		],
+		47
+	);
+};
+
+export default main;
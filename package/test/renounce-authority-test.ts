@@ -0,0 +1,88 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing and finalizing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				dataAccount.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("permanent"),
+				0,
+				false,
+				false
+			),
+			DataProgram.finalizeDataAccount(authority, dataAccount.publicKey),
+		],
+		[feePayer]
+	);
+
+	console.log("simulate renouncing as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.renounceAuthority(secondary.publicKey, dataAccount.publicKey)],
+		6
+	);
+
+	console.log("renouncing authority");
+	await send(
+		connection,
+		[DataProgram.renounceAuthority(authority, dataAccount.publicKey)],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.authority === PublicKey.default.toBase58() &&
+			meta.writeAuthority === null &&
+			meta.finalizeAuthority === null &&
+			meta.closeAuthority === null,
+		`authority wasn't renounced`
+	);
+
+	console.log("simulate closing the ownerless account");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.closeDataAccount(authority, dataAccount.publicKey)],
+		6
+	);
+	console.log("simulate transferring the ownerless account");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.updateDataAccountAuthority(
+				authority,
+				dataAccount.publicKey,
+				secondary.publicKey
+			),
+		],
+		6
+	);
+	console.log("simulate renouncing the ownerless account again");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.renounceAuthority(authority, dataAccount.publicKey)],
+		6
+	);
+};
+
+export default main;
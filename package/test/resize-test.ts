@@ -0,0 +1,148 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const MAX_PERMITTED_DATA_INCREASE = 10240;
+
+const dataOf = async (connection: Connection, dataAccount: PublicKey) =>
+	(await DataProgram.parseData(connection, dataAccount, "confirmed")) as Buffer;
+
+const main = async (connection: Connection, feePayer: Keypair) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				dataAccount.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("hello"),
+				0,
+				false,
+				false
+			),
+		],
+		[feePayer]
+	);
+
+	console.log("growing the data account");
+	await send(
+		connection,
+		[DataProgram.resizeDataAccount(authority, dataAccount.publicKey, 10)],
+		[feePayer]
+	);
+	let data = await dataOf(connection, dataAccount.publicKey);
+	assert(
+		data.equals(Buffer.concat([Buffer.from("hello"), Buffer.alloc(5)])),
+		`data wasn't zero-filled, got ${data.toString("hex")}`
+	);
+
+	console.log("truncating the data account");
+	const balance = await connection.getBalance(
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	await send(
+		connection,
+		[DataProgram.resizeDataAccount(authority, dataAccount.publicKey, 3)],
+		[feePayer]
+	);
+	data = await dataOf(connection, dataAccount.publicKey);
+	assert(data.equals(Buffer.from("hel")), `data wasn't truncated`);
+	const refunded = await connection.getBalance(
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(refunded < balance, `rent wasn't refunded`);
+
+	console.log("simulate growing past the realloc limit");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.resizeDataAccount(
+				authority,
+				dataAccount.publicKey,
+				3 + MAX_PERMITTED_DATA_INCREASE + 1
+			),
+		],
+		21
+	);
+	console.log("simulate resizing as a different authority");
+	const stranger = new Keypair().publicKey;
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.resizeDataAccount(stranger, dataAccount.publicKey, 0)],
+		6
+	);
+
+	console.log("making the data account static");
+	await send(
+		connection,
+		[DataProgram.setDynamic(authority, dataAccount.publicKey, false)],
+		[feePayer]
+	);
+	let meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(!meta.isDynamic, `data account is still dynamic`);
+	console.log("simulate writing past the end of the static account");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				dataAccount.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("hello"),
+				0,
+				false,
+				false
+			),
+		],
+		12
+	);
+
+	console.log("making the data account dynamic again");
+	await send(
+		connection,
+		[DataProgram.setDynamic(authority, dataAccount.publicKey, true)],
+		[feePayer]
+	);
+	meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(meta.isDynamic, `data account is still static`);
+
+	console.log("simulate switching modes as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setDynamic(stranger, dataAccount.publicKey, false)],
+		6
+	);
+	console.log("simulate switching modes of a finalized account");
+	await send(
+		connection,
+		[DataProgram.finalizeDataAccount(authority, dataAccount.publicKey)],
+		[feePayer]
+	);
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setDynamic(authority, dataAccount.publicKey, false)],
+		10
+	);
+};
+
+export default main;
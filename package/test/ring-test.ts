@@ -0,0 +1,120 @@
+import { Connection, Keypair } from "@solana/web3.js";
+import { DataProgram, DataTypeOption, RING_HEADER_SIZE } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing data accounts");
+	const ring = await initializeDataAccount(connection, feePayer);
+	const file = await initializeDataAccount(connection, feePayer);
+
+	console.log("simulate initializing a ring buffer without records");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.initializeRingBuffer(authority, ring.publicKey, 0, 2)],
+		66
+	);
+	console.log("simulate pushing to a data account that is not a ring buffer");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.pushRecord(authority, file.publicKey, Buffer.from("aaaa"))],
+		65
+	);
+
+	console.log("initializing a ring buffer of 2 records of 4 bytes");
+	await send(
+		connection,
+		[DataProgram.initializeRingBuffer(authority, ring.publicKey, 4, 2)],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		ring.publicKey,
+		"confirmed"
+	);
+	assert(meta.dataType === DataTypeOption.RING, `data type isn't RING`);
+
+	console.log("pushing records past the capacity");
+	await send(
+		connection,
+		["aaaa", "bbbb", "cccc"].map((record) =>
+			DataProgram.pushRecord(authority, ring.publicKey, Buffer.from(record))
+		),
+		[feePayer]
+	);
+	const ringBuffer = DataProgram.parseRingBuffer(
+		(await DataProgram.parseData(
+			connection,
+			ring.publicKey,
+			"confirmed"
+		)) as Buffer
+	);
+	assert(
+		ringBuffer.count === 2 &&
+			ringBuffer.head === 1 &&
+			ringBuffer.records[0].equals(Buffer.from("bbbb")) &&
+			ringBuffer.records[1].equals(Buffer.from("cccc")),
+		`ring buffer didn't match`
+	);
+
+	console.log("simulate pushing a record of the wrong size");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.pushRecord(authority, ring.publicKey, Buffer.from("dd"))],
+		67
+	);
+	console.log("simulate pushing as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.pushRecord(
+				secondary.publicKey,
+				ring.publicKey,
+				Buffer.from("dddd")
+			),
+		],
+		6
+	);
+	console.log("simulate updating the ring buffer directly");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				ring.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("dddd"),
+				0,
+				false,
+				false
+			),
+		],
+		64
+	);
+
+	console.log("simulate pushing after freezing the header");
+	await send(
+		connection,
+		[DataProgram.freezeRange(authority, ring.publicKey, 0, RING_HEADER_SIZE)],
+		[feePayer]
+	);
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.pushRecord(authority, ring.publicKey, Buffer.from("dddd"))],
+		37
+	);
+};
+
+export default main;
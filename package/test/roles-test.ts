@@ -0,0 +1,182 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption, RoleOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const writeIx = (authority: PublicKey, dataAccount: PublicKey) =>
+	DataProgram.updateDataAccount(
+		authority,
+		dataAccount,
+		DataTypeOption.CUSTOM,
+		Buffer.from("hello"),
+		0,
+		false,
+		false
+	);
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("transferring the write role");
+	await send(
+		connection,
+		[
+			DataProgram.transferRole(
+				authority,
+				dataAccount.publicKey,
+				secondary.publicKey,
+				RoleOption.WRITE
+			),
+		],
+		[feePayer, secondary]
+	);
+	let meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.writeAuthority === secondary.publicKey.toBase58(),
+		`write role wasn't transferred`
+	);
+	console.log("writing as the write authority");
+	await send(
+		connection,
+		[writeIx(secondary.publicKey, dataAccount.publicKey)],
+		[secondary]
+	);
+	console.log("simulate writing as the authority without the write role");
+	await expectError(
+		connection,
+		authority,
+		[writeIx(authority, dataAccount.publicKey)],
+		6
+	);
+
+	console.log("taking the write role back as the authority manager");
+	await send(
+		connection,
+		[
+			DataProgram.transferRole(
+				authority,
+				dataAccount.publicKey,
+				authority,
+				RoleOption.WRITE
+			),
+		],
+		[feePayer]
+	);
+	console.log("simulate transferring a role as a different authority");
+	const stranger = new Keypair().publicKey;
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.transferRole(
+				stranger,
+				dataAccount.publicKey,
+				stranger,
+				RoleOption.CLOSE
+			),
+		],
+		6
+	);
+	console.log("simulate renouncing a role as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.renounceRole(
+				stranger,
+				dataAccount.publicKey,
+				RoleOption.CLOSE
+			),
+		],
+		6
+	);
+
+	console.log("renouncing the close role");
+	await send(
+		connection,
+		[
+			DataProgram.renounceRole(
+				authority,
+				dataAccount.publicKey,
+				RoleOption.CLOSE
+			),
+		],
+		[feePayer]
+	);
+	meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.closeAuthority === null &&
+			meta.writeAuthority === authority.toBase58(),
+		`roles didn't match`
+	);
+	console.log("simulate closing without the close role");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.closeDataAccount(authority, dataAccount.publicKey)],
+		6
+	);
+	console.log("simulate transferring a renounced role");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.transferRole(
+				authority,
+				dataAccount.publicKey,
+				authority,
+				RoleOption.CLOSE
+			),
+		],
+		38
+	);
+	console.log("writing after renouncing the close role");
+	await send(
+		connection,
+		[writeIx(authority, dataAccount.publicKey)],
+		[feePayer]
+	);
+
+	console.log("updating the authority that holds the write role");
+	await send(
+		connection,
+		[
+			DataProgram.updateDataAccountAuthority(
+				authority,
+				dataAccount.publicKey,
+				secondary.publicKey
+			),
+		],
+		[feePayer, secondary]
+	);
+	console.log("simulate writing as the old authority");
+	await expectError(
+		connection,
+		authority,
+		[writeIx(authority, dataAccount.publicKey)],
+		6
+	);
+	console.log("writing as the new authority");
+	await send(
+		connection,
+		[writeIx(secondary.publicKey, dataAccount.publicKey)],
+		[secondary]
+	);
+};
+
+export default main;
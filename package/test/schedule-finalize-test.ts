@@ -0,0 +1,115 @@
+import { Connection, Keypair } from "@solana/web3.js";
+import { DataProgram, DataStatusOption, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	const now = Math.floor(Date.now() / 1000);
+
+	console.log("simulate scheduling as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.scheduleFinalize(
+				secondary.publicKey,
+				dataAccount.publicKey,
+				now,
+				0
+			),
+		],
+		6
+	);
+
+	console.log("scheduling finalization in an hour");
+	await send(
+		connection,
+		[
+			DataProgram.scheduleFinalize(
+				authority,
+				dataAccount.publicKey,
+				now + 3600,
+				0
+			),
+		],
+		[feePayer]
+	);
+	const meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(meta.finalizeAt === now + 3600, `finalize at didn't match`);
+	console.log("simulate postponing the scheduled finalization");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.scheduleFinalize(
+				authority,
+				dataAccount.publicKey,
+				now + 7200,
+				0
+			),
+		],
+		70
+	);
+	console.log("simulate cranking before the scheduled finalization");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.finalizeScheduled(dataAccount.publicKey)],
+		34
+	);
+
+	console.log("bringing the finalization forward to the current slot");
+	const slot = await connection.getSlot("confirmed");
+	await send(
+		connection,
+		[DataProgram.scheduleFinalize(authority, dataAccount.publicKey, 0, slot)],
+		[feePayer]
+	);
+	console.log("simulate updating after the scheduled finalization");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				dataAccount.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.from("hello"),
+				0,
+				false,
+				false
+			),
+		],
+		10
+	);
+
+	console.log("cranking the scheduled finalization as anyone");
+	await send(
+		connection,
+		[DataProgram.finalizeScheduled(dataAccount.publicKey)],
+		[secondary]
+	);
+	const finalized = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		finalized.dataStatus === DataStatusOption.FINALIZED,
+		`data status didn't match - expected ${DataStatusOption.FINALIZED}, got ${finalized.dataStatus}`
+	);
+};
+
+export default main;
@@ -0,0 +1,196 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import {
+	DataProgram,
+	DataTypeOption,
+	SessionInstructionOption,
+} from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+/**
+ * Returns an `UpdateDataAccount` instruction signed by the session key, whose
+ * usage is charged to the authority
+ */
+const sessionWriteIx = (
+	sessionKey: PublicKey,
+	dataAccount: PublicKey,
+	authority: PublicKey,
+	data: Buffer,
+	offset: number
+) =>
+	DataProgram.withSession(
+		DataProgram.updateDataAccount(
+			sessionKey,
+			dataAccount,
+			DataTypeOption.CUSTOM,
+			data,
+			offset,
+			false,
+			false
+		),
+		dataAccount,
+		sessionKey,
+		authority
+	);
+
+const sessionReserveIx = (
+	sessionKey: PublicKey,
+	dataAccount: PublicKey,
+	authority: PublicKey,
+	space: number
+) =>
+	DataProgram.withSession(
+		DataProgram.reserveSpace(sessionKey, dataAccount, space),
+		dataAccount,
+		sessionKey,
+		authority
+	);
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const sessionKey = secondary.publicKey;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+	const slot = await connection.getSlot("confirmed");
+
+	console.log("simulate creating a session as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.createSession(
+				sessionKey,
+				dataAccount.publicKey,
+				sessionKey,
+				slot + 1000,
+				10,
+				[SessionInstructionOption.UPDATE]
+			),
+		],
+		6
+	);
+
+	console.log("creating a session that can only update 10 bytes");
+	await send(
+		connection,
+		[
+			DataProgram.createSession(
+				authority,
+				dataAccount.publicKey,
+				sessionKey,
+				slot + 1000,
+				10,
+				[SessionInstructionOption.UPDATE]
+			),
+		],
+		[feePayer]
+	);
+
+	console.log("updating as the session key");
+	await send(
+		connection,
+		[
+			sessionWriteIx(
+				sessionKey,
+				dataAccount.publicKey,
+				authority,
+				Buffer.from("hello"),
+				0
+			),
+		],
+		[secondary]
+	);
+	const data = (await DataProgram.parseData(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	)) as Buffer;
+	assert(data.equals(Buffer.from("hello")), `session update didn't match`);
+
+	console.log("simulate reserving space as the session key");
+	await expectError(
+		connection,
+		sessionKey,
+		[sessionReserveIx(sessionKey, dataAccount.publicKey, authority, 100)],
+		50
+	);
+	console.log("simulate updating past the session budget");
+	await expectError(
+		connection,
+		sessionKey,
+		[
+			sessionWriteIx(
+				sessionKey,
+				dataAccount.publicKey,
+				authority,
+				Buffer.from(" world"),
+				5
+			),
+		],
+		51
+	);
+
+	console.log("revoking the session");
+	await send(
+		connection,
+		[DataProgram.revokeSession(authority, dataAccount.publicKey, sessionKey)],
+		[feePayer]
+	);
+	console.log("simulate updating with a revoked session");
+	await expectError(
+		connection,
+		sessionKey,
+		[
+			sessionWriteIx(
+				sessionKey,
+				dataAccount.publicKey,
+				authority,
+				Buffer.from("!"),
+				5
+			),
+		],
+		48
+	);
+
+	console.log("simulate updating with an expired session");
+	await send(
+		connection,
+		[
+			DataProgram.createSession(
+				authority,
+				dataAccount.publicKey,
+				sessionKey,
+				1,
+				0,
+				[SessionInstructionOption.UPDATE]
+			),
+		],
+		[feePayer]
+	);
+	await expectError(
+		connection,
+		sessionKey,
+		[
+			sessionWriteIx(
+				sessionKey,
+				dataAccount.publicKey,
+				authority,
+				Buffer.from("!"),
+				5
+			),
+		],
+		49
+	);
+	await send(
+		connection,
+		[DataProgram.revokeSession(authority, dataAccount.publicKey, sessionKey)],
+		[feePayer]
+	);
+};
+
+export default main;
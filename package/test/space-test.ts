@@ -0,0 +1,83 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const MAX_PERMITTED_DATA_INCREASE = 10240;
+
+const dataLen = async (connection: Connection, dataAccount: PublicKey) =>
+	(await connection.getAccountInfo(dataAccount, "confirmed"))?.data.length;
+
+const main = async (connection: Connection, feePayer: Keypair) => {
+	const authority = feePayer.publicKey;
+	const space = MAX_PERMITTED_DATA_INCREASE + 1000;
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("reserving space past the realloc limit");
+	await send(
+		connection,
+		[DataProgram.reserveSpace(authority, dataAccount.publicKey, space)],
+		[feePayer]
+	);
+	let len = await dataLen(connection, dataAccount.publicKey);
+	assert(
+		len === MAX_PERMITTED_DATA_INCREASE,
+		`data len didn't match - expected ${MAX_PERMITTED_DATA_INCREASE}, got ${len}`
+	);
+	await send(
+		connection,
+		[DataProgram.reserveSpace(authority, dataAccount.publicKey, space)],
+		[feePayer]
+	);
+	len = await dataLen(connection, dataAccount.publicKey);
+	assert(
+		len === space,
+		`data len didn't match - expected ${space}, got ${len}`
+	);
+
+	console.log("reserving less space than the account already has");
+	await send(
+		connection,
+		[DataProgram.reserveSpace(authority, dataAccount.publicKey, 10)],
+		[feePayer]
+	);
+	len = await dataLen(connection, dataAccount.publicKey);
+	assert(
+		len === space,
+		`data len didn't match - expected ${space}, got ${len}`
+	);
+
+	console.log("simulate reserving space as a different authority");
+	const stranger = new Keypair().publicKey;
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.reserveSpace(stranger, dataAccount.publicKey, 2 * space)],
+		6
+	);
+
+	console.log("simulate growing past the realloc limit in a single update");
+	const otherAccount = await initializeDataAccount(connection, feePayer);
+	await expectError(
+		connection,
+		authority,
+		[
+			DataProgram.updateDataAccount(
+				authority,
+				otherAccount.publicKey,
+				DataTypeOption.CUSTOM,
+				Buffer.alloc(1),
+				MAX_PERMITTED_DATA_INCREASE,
+				false,
+				false,
+				true,
+				true
+			),
+		],
+		21
+	);
+};
+
+export default main;
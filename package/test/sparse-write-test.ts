@@ -0,0 +1,93 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+const writeIx = (
+	authority: PublicKey,
+	dataAccount: PublicKey,
+	data: Buffer,
+	offset: number,
+	allowSparse = false,
+	fillByte = 0
+) =>
+	DataProgram.updateDataAccount(
+		authority,
+		dataAccount,
+		DataTypeOption.CUSTOM,
+		data,
+		offset,
+		false,
+		false,
+		true,
+		allowSparse,
+		fillByte
+	);
+
+const main = async (connection: Connection, feePayer: Keypair) => {
+	const authority = feePayer.publicKey;
+
+	console.log("initializing dynamic and static data accounts");
+	const dynamicAccount = await initializeDataAccount(connection, feePayer);
+	const staticAccount = await initializeDataAccount(
+		connection,
+		feePayer,
+		false,
+		8
+	);
+	await send(
+		connection,
+		[writeIx(authority, dynamicAccount.publicKey, Buffer.from("ab"), 0)],
+		[feePayer]
+	);
+
+	console.log("simulate writing past the end without allowing sparse writes");
+	await expectError(
+		connection,
+		authority,
+		[writeIx(authority, dynamicAccount.publicKey, Buffer.from("cd"), 4)],
+		23
+	);
+
+	console.log("writing past the end with a fill byte");
+	await send(
+		connection,
+		[
+			writeIx(
+				authority,
+				dynamicAccount.publicKey,
+				Buffer.from("cd"),
+				4,
+				true,
+				0xff
+			),
+		],
+		[feePayer]
+	);
+	const data = (await DataProgram.parseData(
+		connection,
+		dynamicAccount.publicKey,
+		"confirmed"
+	)) as Buffer;
+	const expected = Buffer.concat([
+		Buffer.from("ab"),
+		Buffer.from([0xff, 0xff]),
+		Buffer.from("cd"),
+	]);
+	assert(
+		data.equals(expected),
+		`data didn't match - expected ${expected.toString(
+			"hex"
+		)}, got ${data.toString("hex")}`
+	);
+
+	console.log("simulate writing past the end of a static account");
+	await expectError(
+		connection,
+		authority,
+		[writeIx(authority, staticAccount.publicKey, Buffer.from("cd"), 16, true)],
+		12
+	);
+};
+
+export default main;
@@ -0,0 +1,234 @@
+import {
+	createMint,
+	getOrCreateAssociatedTokenAccount,
+	mintTo,
+} from "@solana/spl-token";
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { DataProgram, DataTypeOption, RoleOption } from "../src/index";
+import { expectError, initializeDataAccount, send } from "./util/instructions";
+import { assert } from "./util/utils";
+
+/**
+ * Returns an `UpdateDataAccount` instruction signed by a token holder, whose
+ * token account is found by its owner and mint
+ */
+const holderWriteIx = (
+	holder: PublicKey,
+	dataAccount: PublicKey,
+	tokenAccount: PublicKey
+) =>
+	DataProgram.withTokenAccount(
+		DataProgram.updateDataAccount(
+			holder,
+			dataAccount,
+			DataTypeOption.CUSTOM,
+			Buffer.from("gated"),
+			0,
+			false,
+			false
+		),
+		tokenAccount
+	);
+
+const holderReserveIx = (
+	holder: PublicKey,
+	dataAccount: PublicKey,
+	tokenAccount: PublicKey
+) =>
+	DataProgram.withTokenAccount(
+		DataProgram.reserveSpace(holder, dataAccount, 100),
+		tokenAccount
+	);
+
+const main = async (
+	connection: Connection,
+	feePayer: Keypair,
+	secondary: Keypair
+) => {
+	const authority = feePayer.publicKey;
+	const holder = secondary.publicKey;
+
+	console.log("minting gate and other tokens to the holder");
+	const mint = await createMint(connection, feePayer, authority, null, 0);
+	const otherMint = await createMint(connection, feePayer, authority, null, 0);
+	const tokenAccount = await getOrCreateAssociatedTokenAccount(
+		connection,
+		feePayer,
+		mint,
+		holder
+	);
+	const otherTokenAccount = await getOrCreateAssociatedTokenAccount(
+		connection,
+		feePayer,
+		otherMint,
+		holder
+	);
+	await mintTo(connection, feePayer, mint, tokenAccount.address, feePayer, 5);
+	await mintTo(
+		connection,
+		feePayer,
+		otherMint,
+		otherTokenAccount.address,
+		feePayer,
+		5
+	);
+
+	console.log("initializing data account");
+	const dataAccount = await initializeDataAccount(connection, feePayer);
+
+	console.log("simulate setting a token gate as a different authority");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setTokenGate(holder, dataAccount.publicKey, mint, 5)],
+		6
+	);
+	console.log("simulate setting a token gate without a minimum amount");
+	await expectError(
+		connection,
+		authority,
+		[DataProgram.setTokenGate(authority, dataAccount.publicKey, mint, 0)],
+		55
+	);
+
+	console.log("gating updates on holding 10 tokens");
+	await send(
+		connection,
+		[DataProgram.setTokenGate(authority, dataAccount.publicKey, mint, 10)],
+		[feePayer]
+	);
+	let meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(
+		meta.tokenGate?.mint === mint.toBase58() &&
+			meta.tokenGate?.minAmount === "10",
+		`token gate didn't match`
+	);
+	console.log("simulate updating without enough tokens");
+	await expectError(
+		connection,
+		holder,
+		[
+			holderWriteIx(holder, dataAccount.publicKey, tokenAccount.address),
+		],
+		57
+	);
+
+	console.log("gating updates on holding 5 tokens");
+	await send(
+		connection,
+		[DataProgram.setTokenGate(authority, dataAccount.publicKey, mint, 5)],
+		[feePayer]
+	);
+	console.log("simulate updating with tokens of a different mint");
+	await expectError(
+		connection,
+		holder,
+		[
+			holderWriteIx(holder, dataAccount.publicKey, otherTokenAccount.address),
+		],
+		6
+	);
+	console.log("simulate reserving space as a token holder");
+	await expectError(
+		connection,
+		holder,
+		[
+			holderReserveIx(holder, dataAccount.publicKey, tokenAccount.address),
+		],
+		6
+	);
+	console.log("updating as a token holder");
+	await send(
+		connection,
+		[
+			holderWriteIx(holder, dataAccount.publicKey, tokenAccount.address),
+		],
+		[secondary]
+	);
+	const data = (await DataProgram.parseData(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	)) as Buffer;
+	assert(data.equals(Buffer.from("gated")), `gated update didn't match`);
+
+	console.log("removing the token gate");
+	await send(
+		connection,
+		[
+			DataProgram.setTokenGate(
+				authority,
+				dataAccount.publicKey,
+				PublicKey.default,
+				0
+			),
+		],
+		[feePayer]
+	);
+	meta = await DataProgram.parseMetadata(
+		connection,
+		dataAccount.publicKey,
+		"confirmed"
+	);
+	assert(meta.tokenGate === null, `token gate wasn't removed`);
+	console.log("simulate updating as a token holder without a token gate");
+	await expectError(
+		connection,
+		holder,
+		[
+			holderWriteIx(holder, dataAccount.publicKey, tokenAccount.address),
+		],
+		6
+	);
+
+	console.log("gating updates again and renouncing the write role");
+	await send(
+		connection,
+		[
+			DataProgram.setTokenGate(authority, dataAccount.publicKey, mint, 5),
+			DataProgram.renounceRole(
+				authority,
+				dataAccount.publicKey,
+				RoleOption.WRITE
+			),
+		],
+		[feePayer]
+	);
+	console.log("simulate updating as a token holder after renouncing writes");
+	await expectError(
+		connection,
+		holder,
+		[holderWriteIx(holder, dataAccount.publicKey, tokenAccount.address)],
+		6
+	);
+
+	console.log("renouncing the authority of a gated data account");
+	const renouncedAccount = await initializeDataAccount(connection, feePayer);
+	await send(
+		connection,
+		[
+			DataProgram.setTokenGate(authority, renouncedAccount.publicKey, mint, 5),
+			DataProgram.renounceAuthority(authority, renouncedAccount.publicKey),
+		],
+		[feePayer]
+	);
+	meta = await DataProgram.parseMetadata(
+		connection,
+		renouncedAccount.publicKey,
+		"confirmed"
+	);
+	assert(meta.tokenGate === null, `token gate wasn't cleared on renounce`);
+	console.log("simulate updating as a token holder after renouncing");
+	await expectError(
+		connection,
+		holder,
+		[holderWriteIx(holder, renouncedAccount.publicKey, tokenAccount.address)],
+		6
+	);
+};
+
+export default main;
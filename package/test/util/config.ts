@@ -0,0 +1,34 @@
+import { Connection, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { DataProgram, IDataProgramConfig } from "../../src/index";
+
+const readU64 = (data: Buffer, offset: number) =>
+	new BN(data.subarray(offset, offset + 8), "le").toNumber();
+
+/**
+ * Returns the settings stored in the Config PDA Account of the program
+ */
+export const getConfig = async (
+	connection: Connection
+): Promise<IDataProgramConfig> => {
+	const [config] = DataProgram.getConfigPDA();
+	const data = (await connection.getAccountInfo(config, "confirmed"))?.data;
+	if (!data) {
+		throw new Error("config pda is not initialized");
+	}
+	const feeExempt: PublicKey[] = [];
+	for (let i = 0; i < data.readUInt32LE(98); i++) {
+		const offset = 102 + i * 32;
+		feeExempt.push(new PublicKey(data.subarray(offset, offset + 32)));
+	}
+	return {
+		admin: new PublicKey(data.subarray(0, 32)),
+		isPaused: data[32] === 1,
+		maxAccountSize: readU64(data, 33),
+		maxWriteSize: readU64(data, 41),
+		protocolFee: readU64(data, 49),
+		treasury: new PublicKey(data.subarray(57, 89)),
+		storageFeePerByte: readU64(data, 90),
+		feeExempt,
+	};
+};
@@ -0,0 +1,93 @@
+import {
+	AccountMeta,
+	ConfirmOptions,
+	Connection,
+	Keypair,
+	PublicKey,
+	SimulateTransactionConfig,
+	Transaction,
+	TransactionInstruction,
+	TransactionMessage,
+	VersionedTransaction,
+	sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { DataProgram } from "../../src/index";
+import { assert } from "./utils";
+
+/**
+ * Returns the Usage PDA Account of the authority as a writable account
+ */
+export const usage = (authority: PublicKey): AccountMeta => ({
+	pubkey: DataProgram.getUsagePDA(authority)[0],
+	isSigner: false,
+	isWritable: true,
+});
+
+/**
+ * Sends and confirms the instructions in a single transaction and returns its
+ * signature
+ */
+export const send = async (
+	connection: Connection,
+	instructions: TransactionInstruction[],
+	signers: Keypair[]
+) => {
+	const tx = new Transaction();
+	tx.add(...instructions);
+	return await sendAndConfirmTransaction(connection, tx, signers, {
+		skipPreflight: true,
+		preflightCommitment: "confirmed",
+		commitment: "confirmed",
+	} as ConfirmOptions);
+};
+
+/**
+ * Simulates the instructions in a single transaction and asserts that they
+ * fail with the custom error code of the `DataAccountError`
+ */
+export const expectError = async (
+	connection: Connection,
+	payer: PublicKey,
+	instructions: TransactionInstruction[],
+	code: number
+) => {
+	const messageV0 = new TransactionMessage({
+		payerKey: payer,
+		recentBlockhash: (await connection.getLatestBlockhash("confirmed"))
+			.blockhash,
+		instructions,
+	}).compileToV0Message();
+	const simulateData = await connection.simulateTransaction(
+		new VersionedTransaction(messageV0),
+		{ sigVerify: false, commitment: "confirmed" } as SimulateTransactionConfig
+	);
+	assert(simulateData.value.err != null, `simulate did not error out`);
+	assert(
+		JSON.stringify(simulateData.value.err).indexOf(`"Custom":${code}`) != -1,
+		`simulate error ("Custom":${code}) was not found, got ${JSON.stringify(
+			simulateData.value.err
+		)}`
+	);
+};
+
+/**
+ * Initializes a new Data Account of the authority and returns its `Keypair`
+ */
+export const initializeDataAccount = async (
+	connection: Connection,
+	authority: Keypair,
+	isDynamic = true,
+	initialSize = 0
+) => {
+	const dataAccount = new Keypair();
+	const initializeIx = DataProgram.initializeDataAccount(
+		authority.publicKey,
+		dataAccount.publicKey,
+		authority.publicKey,
+		false,
+		isDynamic,
+		initialSize
+	);
+	await send(connection, [initializeIx], [authority, dataAccount]);
+	return dataAccount;
+};
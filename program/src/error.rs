@@ -30,6 +30,130 @@ pub enum DataAccountError {
     Overflow,
     #[error("Data account should have sufficient space")]
     InsufficientSpace,
+    #[error("Data account should be a directory")]
+    NotDirectory,
+    #[error("Directory entry name should be valid")]
+    InvalidEntryName,
+    #[error("Directory entry should not already exist")]
+    EntryAlreadyExists,
+    #[error("Directory entry should exist")]
+    EntryNotFound,
+    #[error("Path should be valid")]
+    InvalidPath,
+    #[error("Data account should be a manifest")]
+    NotManifest,
+    #[error("Shard should not already be part of the manifest")]
+    DuplicateShard,
+    #[error("Shard should match its manifest entry")]
+    ShardMismatch,
+    #[error("Data account should not grow by more than the realloc limit in one instruction")]
+    ReallocLimitExceeded,
+    #[error("Account should be owned by the data program")]
+    InvalidOwner,
+    #[error("Data account should not be left with a gap unless sparse writes are allowed")]
+    SparseWrite,
+    #[error("Account should be the config PDA of the data program")]
+    InvalidConfig,
+    #[error("Account should be the admin of the data program")]
+    InvalidAdmin,
+    #[error("Data program should not be paused")]
+    ProgramPaused,
+    #[error("Data account should not exceed the maximum account size")]
+    AccountSizeLimitExceeded,
+    #[error("Data should not exceed the maximum write size")]
+    WriteSizeLimitExceeded,
+    #[error("Account should be the treasury of the data program")]
+    InvalidTreasury,
+    #[error("Account should be the usage PDA of the authority")]
+    InvalidUsage,
+    #[error("Authority should not allocate more than its usage quota")]
+    QuotaExceeded,
+    #[error("Data account should be expired")]
+    NotExpired,
+    #[error("Account should be the beneficiary of the data account")]
+    InvalidBeneficiary,
+    #[error("Data account should be scheduled to be finalized by now")]
+    FinalizeNotDue,
+    #[error("Frozen range should be non-empty and within the data")]
+    InvalidFrozenRange,
+    #[error("Data account should have room for another frozen range")]
+    TooManyFrozenRanges,
+    #[error("Data should not be modified within a frozen range")]
+    RangeFrozen,
+    #[error("Role should not be renounced")]
+    RoleRenounced,
+    #[error(
+        "Guardians should be distinct and no more than the maximum with a reachable threshold and a non-zero delay"
+    )]
+    InvalidGuardians,
+    #[error("Recovery should be initiated by a quorum of guardians")]
+    QuorumNotReached,
+    #[error("Data account should have a pending recovery")]
+    NoPendingRecovery,
+    #[error("Recovery delay should have passed")]
+    RecoveryNotDue,
+    #[error("Account should be the arbiter of the data account")]
+    InvalidArbiter,
+    #[error("Data account should not be frozen by its arbiter")]
+    AccountFrozen,
+    #[error("Relayed update should be signed by the write authority via the ed25519 program")]
+    InvalidRelaySignature,
+    #[error("Relayed update should be signed with the current nonce of the data account")]
+    InvalidNonce,
+    #[error("Relayed update should not be expired")]
+    RelayExpired,
+    #[error("Account should be a session PDA of the write authority for the data account")]
+    InvalidSession,
+    #[error("Session key should not be expired")]
+    SessionExpired,
+    #[error("Session key should be allowed to sign the instruction")]
+    SessionNotAllowed,
+    #[error("Session key should not write more than its byte budget")]
+    SessionBudgetExceeded,
+    #[error("Account should be a delegation PDA of the write authority for the data account")]
+    InvalidDelegation,
+    #[error("Delegate ranges should be non-empty and no more than the maximum")]
+    InvalidDelegateRange,
+    #[error("Delegated write should stay within the approved byte ranges")]
+    OutsideDelegateRange,
+    #[error("Token gate should require at least one token of a mint")]
+    InvalidTokenGate,
+    #[error("Account should be a token account of the token gate mint owned by the signer")]
+    InvalidTokenAccount,
+    #[error("Token account should hold at least the minimum amount of the token gate")]
+    InsufficientTokens,
+    #[error("Mint should be an spl token mint with a supply of one and no decimals")]
+    InvalidNftMint,
+    #[error("Data account should not be an append-only log")]
+    AppendOnly,
+    #[error("Data account should be an append-only log")]
+    NotLog,
+    #[error("Data account should be empty to become an append-only log")]
+    LogNotEmpty,
+    #[error("Log record should be length-prefixed and fit the log")]
+    InvalidLogRecord,
+    #[error("Append price should be paid to the treasury of the log or its authority")]
+    InvalidAppendTreasury,
+    #[error("Data account should not be a ring buffer")]
+    RingBuffer,
+    #[error("Data account should be a ring buffer")]
+    NotRingBuffer,
+    #[error("Ring buffer should have a non-zero record size and capacity")]
+    InvalidRingBuffer,
+    #[error("Record should be exactly the record size of the ring buffer")]
+    InvalidRecordSize,
+    #[error("Metadata PDA should be migrated to the current layout via MigrateMetadata")]
+    MetadataNotMigrated,
+    #[error("Metadata PDA should not already be migrated to the current layout")]
+    AlreadyMigrated,
+    #[error("Scheduled finalization should only be brought forward, not postponed or cancelled")]
+    FinalizePostponed,
+    #[error("Recovery should hand the data account to an authority other than the default PubKey")]
+    InvalidRecoveryAuthority,
+    #[error("Token holder should only overwrite data in place without changing the data type")]
+    TokenGateInPlace,
+    #[error("Data account should not already be bound to an nft")]
+    AlreadyNftBound,
 }
 
 impl From<DataAccountError> for ProgramError {
@@ -2,37 +2,81 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankInstruction;
 
 use crate::state::{
-    CloseDataAccountArgs, FinalizeDataAccountArgs, InitializeDataAccountArgs,
-    UpdateDataAccountArgs, UpdateDataAccountAuthorityArgs,
+    AppendRecordArgs, AppendShardArgs, ApproveDelegateArgs, BindToNftArgs, CancelRecoveryArgs,
+    CloseDataAccountArgs, CompleteRecoveryArgs, CreateDirectoryEntryArgs, CreateSessionArgs,
+    DelegatedUpdateDataAccountArgs, FinalizeDataAccountArgs, FinalizeManifestArgs,
+    FinalizeScheduledArgs, FreezeDataAccountArgs, FreezeRangeArgs, InitializeConfigArgs,
+    InitializeDataAccountArgs, InitializeRingBufferArgs, InitiateRecoveryArgs, MigrateMetadataArgs,
+    MoveDirectoryEntryArgs, PushRecordArgs, ReclaimExpiredArgs, RelayedUpdateDataAccountArgs,
+    RenameDirectoryEntryArgs, RenounceAuthorityArgs, RenounceRoleArgs, ReserveSpaceArgs,
+    ResizeDataAccountArgs, RevokeDelegateArgs, RevokeSessionArgs, ScheduleFinalizeArgs,
+    SetAppendOnlyArgs, SetAppendPriceArgs, SetArbiterArgs, SetDynamicArgs, SetExpiryArgs,
+    SetGuardiansArgs, SetTokenGateArgs, SetUsageQuotaArgs, ThawDataAccountArgs, TopUpRentArgs,
+    TransferRoleArgs, UnlinkDirectoryEntryArgs, UpdateConfigArgs, UpdateDataAccountArgs,
+    UpdateDataAccountAuthorityArgs, WithdrawExcessLamportsArgs, WithdrawProtocolFeesArgs,
 };
 
 /// Instructions supported by the Data program.
+///
+/// Optional accounts, such as the usage pdas, the config pda, a session pda or a token
+/// account, follow the accounts every instruction requires in any order, as they are found by
+/// their address, or by their owner and mint for token accounts
 #[derive(BorshSerialize, BorshDeserialize, Clone, ShankInstruction)]
 pub enum DataAccountInstruction {
     /// This instruction initializes a data account that is accessible by the authority.
     /// This also sets the owner of the data account to be the data program
     /// If a data account was already initialized for given user, it returns Error
+    /// The protocol and storage fees, if any, are collected by the config pda
     #[account(0, signer, writable, name = "feepayer", desc = "Feepayer account")]
     #[account(1, signer, writable, name = "data", desc = "Data account data")]
     #[account(2, writable, name = "pda", desc = "Data account pda")]
     #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, writable, name = "config", desc = "Config pda")]
     InitializeDataAccount(InitializeDataAccountArgs),
 
     /// This instruction updates the data of the data account corresponding to the authority
     /// Allows user to specify whether the data should be committed or verified
     /// Requires data account to be initialized previously
+    /// A session key can sign instead by passing its session pda
+    /// A holder of the token gate can sign instead by passing its token account, but can only
+    /// overwrite data in place without changing the data type, and only until the write role is
+    /// renounced
+    /// Truncating the data refunds the excess lamports to the authority if it is passed and
+    /// writable, otherwise they stay in the data account
     #[account(0, signer, writable, name = "authority", desc = "Authority account")]
     #[account(1, writable, name = "data", desc = "Data account data")]
     #[account(2, writable, name = "pda", desc = "Data account pda")]
     #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, writable, name = "config", desc = "Config pda")]
     UpdateDataAccount(UpdateDataAccountArgs),
 
-    /// This instruction updates the authority of the data account
+    /// This instruction updates the authority of the data account and moves its usage from the
+    /// old authority to the new authority
     /// Requires data account to be initialized previously
+    /// The old authority must be writable and the system program passed to create the usage pda
+    /// of the new authority
     #[account(0, signer, name = "old_authority", desc = "Old Authority")]
     #[account(1, name = "data", desc = "Data account data")]
     #[account(2, writable, name = "pda", desc = "Data account pda")]
     #[account(3, signer, name = "new_authority", desc = "New Authority")]
+    #[account(4, optional, name = "system_program", desc = "System program")]
+    #[account(
+        5,
+        optional,
+        writable,
+        name = "old_usage",
+        desc = "Old authority usage pda"
+    )]
+    #[account(
+        6,
+        optional,
+        writable,
+        name = "new_usage",
+        desc = "New authority usage pda"
+    )]
+    #[account(7, optional, name = "config", desc = "Config pda")]
     UpdateDataAccountAuthority(UpdateDataAccountAuthorityArgs),
 
     /// This instruction finalizes the data and metadata of the data account
@@ -40,6 +84,7 @@ pub enum DataAccountInstruction {
     #[account(0, signer, name = "authority", desc = "Authority account")]
     #[account(1, name = "data", desc = "Data account data")]
     #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
     FinalizeDataAccount(FinalizeDataAccountArgs),
 
     /// This instruction unlinks the data account corresponding to the authority
@@ -47,5 +92,524 @@ pub enum DataAccountInstruction {
     #[account(0, signer, writable, name = "authority", desc = "Authority account")]
     #[account(1, writable, name = "data", desc = "Data account data")]
     #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
     CloseDataAccount(CloseDataAccountArgs),
+
+    /// This instruction adds an entry pointing at the entry data account to the directory
+    /// Requires both data accounts to be initialized previously and the directory not finalized
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "directory", desc = "Directory data")]
+    #[account(2, writable, name = "directory_pda", desc = "Directory pda")]
+    #[account(3, name = "entry", desc = "Entry data")]
+    #[account(4, name = "entry_pda", desc = "Entry pda")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(7, optional, name = "config", desc = "Config pda")]
+    CreateDirectoryEntry(CreateDirectoryEntryArgs),
+
+    /// This instruction renames an entry of the directory data account
+    /// Requires directory to be initialized previously and not be finalized
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "directory", desc = "Directory data")]
+    #[account(2, writable, name = "directory_pda", desc = "Directory pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, name = "config", desc = "Config pda")]
+    RenameDirectoryEntry(RenameDirectoryEntryArgs),
+
+    /// This instruction moves an entry from the source directory to the destination directory
+    /// Requires both directories to be initialized previously and not be finalized
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "source", desc = "Source directory data")]
+    #[account(2, writable, name = "source_pda", desc = "Source directory pda")]
+    #[account(3, writable, name = "destination", desc = "Destination data")]
+    #[account(4, writable, name = "destination_pda", desc = "Destination pda")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(7, optional, name = "config", desc = "Config pda")]
+    MoveDirectoryEntry(MoveDirectoryEntryArgs),
+
+    /// This instruction removes an entry from the directory data account
+    /// The data account the entry points at is left untouched
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "directory", desc = "Directory data")]
+    #[account(2, writable, name = "directory_pda", desc = "Directory pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, name = "config", desc = "Config pda")]
+    UnlinkDirectoryEntry(UnlinkDirectoryEntryArgs),
+
+    /// This instruction appends the shard data account to the end of the manifest data account
+    /// The shard length is read from the shard data account and the hash is given by the uploader
+    /// Requires both data accounts to be initialized previously and to share the same authority,
+    /// which must also hold the write role of the shard
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "manifest", desc = "Manifest data")]
+    #[account(2, writable, name = "manifest_pda", desc = "Manifest pda")]
+    #[account(3, name = "shard", desc = "Shard data")]
+    #[account(4, name = "shard_pda", desc = "Shard pda")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(7, optional, name = "config", desc = "Config pda")]
+    AppendShard(AppendShardArgs),
+
+    /// This instruction finalizes the manifest data account and all of its shards
+    /// Every shard data account and its pda must be passed in manifest order right after the
+    /// manifest pda, and every shard is hashed to ensure it still matches the manifest
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "manifest", desc = "Manifest data")]
+    #[account(2, writable, name = "manifest_pda", desc = "Manifest pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    FinalizeManifest(FinalizeManifestArgs),
+
+    /// This instruction grows the data account towards the given space by at most
+    /// MAX_PERMITTED_DATA_INCREASE bytes so that large accounts can be pre-sized over several calls
+    /// Requires data account to be initialized previously and not be finalized
+    /// A session key can sign instead by passing its session pda
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, writable, name = "config", desc = "Config pda")]
+    ReserveSpace(ReserveSpaceArgs),
+
+    /// This instruction grows (zero-filling) or truncates the data account to the given length
+    /// The rent exemption is topped up from the signer, while the excess lamports of a truncated
+    /// data account are refunded to the authority if it is passed and writable, otherwise they
+    /// stay in the data account
+    /// Requires data account to be initialized previously and not be finalized
+    /// A session key can sign instead by passing its session pda, which is charged for every byte
+    /// the data account grows or shrinks by
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, writable, name = "config", desc = "Config pda")]
+    ResizeDataAccount(ResizeDataAccountArgs),
+
+    /// This instruction converts the data account between static and dynamic
+    /// Requires data account to be initialized previously and not be finalized
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    SetDynamic(SetDynamicArgs),
+
+    /// This instruction transfers all lamports above the rent exemption of the data account
+    /// and its metadata pda to the recipient
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "recipient", desc = "Recipient account")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    WithdrawExcessLamports(WithdrawExcessLamportsArgs),
+
+    /// This instruction tops up the data account and its metadata pda to be rent exempt
+    /// Can be called by anyone
+    #[account(0, signer, writable, name = "payer", desc = "Payer account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    TopUpRent(TopUpRentArgs),
+
+    /// This instruction creates the program config pda with the given admin
+    /// Requires the signer to be the upgrade authority of the data program
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "upgrade_authority",
+        desc = "Upgrade authority"
+    )]
+    #[account(1, writable, name = "config", desc = "Config pda")]
+    #[account(2, name = "program_data", desc = "Program data account")]
+    #[account(3, name = "system_program", desc = "System program")]
+    InitializeConfig(InitializeConfigArgs),
+
+    /// This instruction updates the admin, pause flag, limits, fees, treasury and fee exemptions
+    /// Requires the config to be initialized previously
+    #[account(0, signer, writable, name = "admin", desc = "Admin account")]
+    #[account(1, writable, name = "config", desc = "Config pda")]
+    #[account(2, name = "system_program", desc = "System program")]
+    UpdateConfig(UpdateConfigArgs),
+
+    /// This instruction transfers the protocol and storage fees collected by the config pda
+    /// to the treasury
+    /// Requires the config to be initialized previously
+    #[account(0, signer, name = "admin", desc = "Admin account")]
+    #[account(1, writable, name = "config", desc = "Config pda")]
+    #[account(2, writable, name = "treasury", desc = "Treasury account")]
+    WithdrawProtocolFees(WithdrawProtocolFeesArgs),
+
+    /// This instruction sets the maximum bytes and data accounts the authority can allocate
+    /// The usage pda of the authority is created if it does not exist yet
+    #[account(0, signer, writable, name = "admin", desc = "Admin account")]
+    #[account(1, name = "config", desc = "Config pda")]
+    #[account(2, name = "authority", desc = "Authority account")]
+    #[account(3, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    SetUsageQuota(SetUsageQuotaArgs),
+
+    /// This instruction sets the slot after which anyone can reclaim the data account and
+    /// the beneficiary that receives its lamports (the authority if not set and not nft-bound)
+    /// Requires data account to be initialized previously
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    SetExpiry(SetExpiryArgs),
+
+    /// This instruction closes an expired data account and its metadata pda, transferring
+    /// their lamports to the beneficiary (or the authority if not set)
+    /// Can be called by anyone
+    #[account(0, writable, name = "beneficiary", desc = "Beneficiary account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    ReclaimExpired(ReclaimExpiredArgs),
+
+    /// This instruction schedules the data account to be finalized at the given unix timestamp
+    /// and/or slot, after which it is treated as finalized
+    /// A scheduled finalization can only be brought forward, never postponed or cancelled
+    /// Requires data account to be initialized previously and not be finalized
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    ScheduleFinalize(ScheduleFinalizeArgs),
+
+    /// This instruction sets the data status of a data account whose scheduled finalization
+    /// has passed to finalized
+    /// Can be called by anyone
+    #[account(0, name = "data", desc = "Data account data")]
+    #[account(1, writable, name = "pda", desc = "Data account pda")]
+    #[account(2, optional, name = "config", desc = "Config pda")]
+    FinalizeScheduled(FinalizeScheduledArgs),
+
+    /// This instruction permanently freezes the [start, end) byte range of the data account
+    /// Requires data account to be initialized previously and not be finalized
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    FreezeRange(FreezeRangeArgs),
+
+    /// This instruction hands a role of the data account to the new holder
+    /// Can be called by the current holder of the role or the authority manager
+    /// Requires the role to not be renounced
+    #[account(0, signer, name = "holder", desc = "Role holder or authority manager")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, signer, name = "new_holder", desc = "New role holder account")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    TransferRole(TransferRoleArgs),
+
+    /// This instruction permanently renounces a role of the data account so that nobody holds it
+    /// Can be called by the current holder of the role or the authority manager
+    #[account(0, signer, name = "holder", desc = "Role holder or authority manager")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    RenounceRole(RenounceRoleArgs),
+
+    /// This instruction permanently renounces the authority, every role and the token gate of
+    /// the data account so that it can never be updated, closed or transferred again
+    /// Can be called by the authority manager
+    #[account(0, signer, name = "authority", desc = "Authority manager account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    RenounceAuthority(RenounceAuthorityArgs),
+
+    /// This instruction sets the recovery guardians of the data account, the number of them
+    /// required to initiate a recovery and the delay before it can be completed
+    /// Can be called by the authority manager and cancels any pending recovery
+    #[account(0, signer, name = "authority", desc = "Authority manager account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    SetGuardians(SetGuardiansArgs),
+
+    /// This instruction starts rotating the authority of the data account to the new authority
+    /// Requires a quorum of guardians to sign, passed after the new authority along with any
+    /// optional account
+    #[account(0, name = "data", desc = "Data account data")]
+    #[account(1, writable, name = "pda", desc = "Data account pda")]
+    #[account(2, name = "new_authority", desc = "New authority account")]
+    #[account(
+        3,
+        signer,
+        name = "guardians",
+        desc = "Guardian accounts, one per guardian"
+    )]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    InitiateRecovery(InitiateRecoveryArgs),
+
+    /// This instruction cancels the pending recovery of the data account
+    /// Can be called by the authority or the authority manager
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    CancelRecovery(CancelRecoveryArgs),
+
+    /// This instruction rotates the authority of the data account once the recovery delay
+    /// has passed and moves its usage from the old authority to the new authority
+    /// Can be called by anyone
+    #[account(0, signer, writable, name = "payer", desc = "Payer account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        optional,
+        writable,
+        name = "old_usage",
+        desc = "Old authority usage pda"
+    )]
+    #[account(
+        5,
+        optional,
+        writable,
+        name = "new_usage",
+        desc = "New authority usage pda"
+    )]
+    #[account(6, optional, name = "config", desc = "Config pda")]
+    CompleteRecovery(CompleteRecoveryArgs),
+
+    /// This instruction designates the arbiter that can freeze the data account
+    /// Can be called by the authority manager if no arbiter is designated and by the arbiter
+    /// itself otherwise
+    #[account(
+        0,
+        signer,
+        name = "authority",
+        desc = "Authority manager or arbiter account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    SetArbiter(SetArbiterArgs),
+
+    /// This instruction freezes the data account, blocking updates, transfers and closing
+    /// until it is thawed
+    /// Can be called by the arbiter
+    #[account(0, signer, name = "arbiter", desc = "Arbiter account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    FreezeDataAccount(FreezeDataAccountArgs),
+
+    /// This instruction thaws a data account frozen by the arbiter
+    /// Can be called by the arbiter
+    #[account(0, signer, name = "arbiter", desc = "Arbiter account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    ThawDataAccount(ThawDataAccountArgs),
+
+    /// This instruction updates the data of a data account on behalf of its write authority,
+    /// who signs a RelayedUpdateMessage off-chain that is verified by the ed25519 program
+    /// instruction right before this one
+    /// The relayer pays all fees and rent
+    #[account(0, signer, writable, name = "relayer", desc = "Relayer account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, name = "instructions", desc = "Instructions sysvar")]
+    #[account(5, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(6, optional, writable, name = "config", desc = "Config pda")]
+    RelayedUpdateDataAccount(RelayedUpdateDataAccountArgs),
+
+    /// This instruction registers a session key that can sign UpdateDataAccount, ReserveSpace
+    /// and ResizeDataAccount in place of the write authority, until it expires or is revoked
+    /// The session pda is then passed along with these instructions
+    /// Registering the same session key again replaces its expiry, budget and instructions
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "authority",
+        desc = "Write authority account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "session", desc = "Session pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(5, optional, name = "config", desc = "Config pda")]
+    CreateSession(CreateSessionArgs),
+
+    /// This instruction revokes a session key and closes its session pda
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "authority",
+        desc = "Write authority account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "session", desc = "Session pda")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    RevokeSession(RevokeSessionArgs),
+
+    /// This instruction approves a program to write to the data account through
+    /// DelegatedUpdateDataAccount, optionally limited to byte ranges
+    /// Approving the same program again replaces its byte ranges
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "authority",
+        desc = "Write authority account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "delegation", desc = "Delegation pda")]
+    #[account(4, name = "system_program", desc = "System program")]
+    #[account(5, optional, name = "config", desc = "Config pda")]
+    ApproveDelegate(ApproveDelegateArgs),
+
+    /// This instruction revokes the approval of a program and closes its delegation pda
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "authority",
+        desc = "Write authority account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, writable, name = "delegation", desc = "Delegation pda")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    RevokeDelegate(RevokeDelegateArgs),
+
+    /// This instruction updates the data of the data account on behalf of an approved program,
+    /// which signs with its delegate signer pda via invoke_signed
+    /// The delegate signer pays for any growth and the data account can not be resized
+    /// nor its data type changed if the program is limited to byte ranges
+    #[account(0, signer, writable, name = "delegate", desc = "Delegate signer pda")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, name = "delegation", desc = "Delegation pda")]
+    #[account(5, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(6, optional, writable, name = "config", desc = "Config pda")]
+    DelegatedUpdateDataAccount(DelegatedUpdateDataAccountArgs),
+
+    /// This instruction sets the mint and minimum amount of tokens that let anyone holding
+    /// them overwrite the data of the data account in place by passing their token account
+    /// Passing the default mint removes the token gate, and token holders can no longer write
+    /// once the write role is renounced
+    #[account(0, signer, name = "authority", desc = "Authority manager account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    SetTokenGate(SetTokenGateArgs),
+
+    /// This instruction binds the data account to an nft so that its authority follows whoever
+    /// holds the nft, handing the holder every role held by the authority itself
+    /// The holder signs any instruction that checks a role by passing its token account
+    /// The mint should have no mint authority and the data account should not be nft-bound yet
+    /// UpdateDataAccountAuthority unbinds the data account
+    /// The usage of the data account moves from the authority to the mint
+    #[account(
+        0,
+        signer,
+        writable,
+        name = "authority",
+        desc = "Authority manager account"
+    )]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "mint", desc = "Nft mint")]
+    #[account(
+        4,
+        name = "token_account",
+        desc = "Nft token account of the authority manager"
+    )]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(
+        6,
+        optional,
+        writable,
+        name = "old_usage",
+        desc = "Authority usage pda"
+    )]
+    #[account(7, optional, writable, name = "new_usage", desc = "Mint usage pda")]
+    #[account(8, optional, name = "config", desc = "Config pda")]
+    BindToNft(BindToNftArgs),
+
+    /// This instruction turns the data account into an append-only log that can only be
+    /// extended via AppendRecord, optionally by anyone
+    /// Existing bytes can never be modified or deleted again, not even by the authority
+    /// Requires data account to be empty unless it is a log already, in which case only
+    /// whether anyone can append is updated
+    #[account(0, signer, name = "authority", desc = "Finalize authority account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    SetAppendOnly(SetAppendOnlyArgs),
+
+    /// This instruction appends a record carrying the appender and the current slot to an
+    /// append-only log
+    /// If the log has an append price, the treasury is passed right after the system program
+    /// and the appender pays it the price
+    /// Unless anyone can append, a session key or token holder can sign instead of the write
+    /// authority by passing its session pda or token account
+    #[account(0, signer, writable, name = "appender", desc = "Appender account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, writable, name = "config", desc = "Config pda")]
+    AppendRecord(AppendRecordArgs),
+
+    /// This instruction sets the lamports an appender pays per record, or per byte of data,
+    /// to append to an append-only log and the treasury they are paid to
+    /// A price of 0 makes appending free and the default treasury pays the current authority,
+    /// which is stored as the treasury
+    #[account(0, signer, name = "authority", desc = "Authority manager account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    SetAppendPrice(SetAppendPriceArgs),
+
+    /// This instruction turns the data account into a ring buffer of capacity records of
+    /// record_size bytes each, resizing it to fit and clearing any previous data
+    /// Calling it on a ring buffer resets it
+    #[account(0, signer, writable, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, writable, name = "usage", desc = "Authority usage pda")]
+    #[account(5, optional, writable, name = "config", desc = "Config pda")]
+    InitializeRingBuffer(InitializeRingBufferArgs),
+
+    /// This instruction writes a record to the head of a ring buffer, overwriting the oldest
+    /// record once the ring buffer is full
+    /// A session key or token holder can sign instead of the write authority by passing its
+    /// session pda or token account
+    /// Fails if the ring header or the slot at the head is frozen
+    #[account(0, signer, name = "authority", desc = "Authority account")]
+    #[account(1, writable, name = "data", desc = "Data account data")]
+    #[account(2, name = "pda", desc = "Data account pda")]
+    #[account(3, optional, name = "config", desc = "Config pda")]
+    PushRecord(PushRecordArgs),
+
+    /// This instruction grows a metadata pda created before the metadata was versioned to the
+    /// current layout, which is required before its metadata can be updated
+    /// Anyone can migrate a metadata pda by paying for its rent
+    #[account(0, signer, writable, name = "payer", desc = "Payer account")]
+    #[account(1, name = "data", desc = "Data account data")]
+    #[account(2, writable, name = "pda", desc = "Data account pda")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, name = "config", desc = "Config pda")]
+    MigrateMetadata(MigrateMetadataArgs),
 }
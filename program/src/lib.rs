@@ -1,5 +1,9 @@
 pub mod entrypoint;
 pub mod error;
 pub mod instruction;
+pub mod log;
+pub mod manifest;
+pub mod namespace;
 pub mod processor;
+pub mod ring;
 pub mod state;
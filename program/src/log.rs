@@ -0,0 +1,147 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::DataAccountError;
+
+/// Size of the header that precedes the data of every log record: the `u32` length of the
+/// data, the appender and the `u64` slot it was appended in
+pub const LOG_RECORD_HEADER_SIZE: usize = 4 + 32 + 8;
+
+/// Record appended to a data account of type `LOG`
+#[derive(PartialEq, Debug, Clone)]
+pub struct LogRecord<'a> {
+    pub appender: Pubkey,
+    pub slot: u64,
+    pub data: &'a [u8],
+}
+
+impl<'a> LogRecord<'a> {
+    /// Get the header to be written right before the data of the record
+    pub fn header(&self) -> Result<[u8; LOG_RECORD_HEADER_SIZE], ProgramError> {
+        let len = u32::try_from(self.data.len()).map_err(|_| DataAccountError::Overflow)?;
+
+        let mut header = [0; LOG_RECORD_HEADER_SIZE];
+        header[..4].copy_from_slice(&len.to_le_bytes());
+        header[4..36].copy_from_slice(self.appender.as_ref());
+        header[36..].copy_from_slice(&self.slot.to_le_bytes());
+        Ok(header)
+    }
+    /// Get the size of the record including its header
+    pub fn size(&self) -> usize {
+        LOG_RECORD_HEADER_SIZE + self.data.len()
+    }
+}
+
+/// Decodes the records of a log in the order they were appended
+///
+/// Yields an error and stops if the data ends in the middle of a record
+pub struct LogRecords<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> LogRecords<'a> {
+    /// Default constructor
+    pub fn new(data: &'a [u8]) -> Self {
+        LogRecords { data }
+    }
+}
+
+impl<'a> Iterator for LogRecords<'a> {
+    type Item = Result<LogRecord<'a>, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let record = self.data.get(..LOG_RECORD_HEADER_SIZE).and_then(|header| {
+            let len = u32::from_le_bytes(header[..4].try_into().ok()?) as usize;
+            let data = self
+                .data
+                .get(LOG_RECORD_HEADER_SIZE..LOG_RECORD_HEADER_SIZE.checked_add(len)?)?;
+            Some(LogRecord {
+                appender: Pubkey::new_from_array(header[4..36].try_into().ok()?),
+                slot: u64::from_le_bytes(header[36..].try_into().ok()?),
+                data,
+            })
+        });
+
+        match record {
+            Some(record) => {
+                self.data = &self.data[record.size()..];
+                Some(Ok(record))
+            }
+            None => {
+                self.data = &[];
+                Some(Err(DataAccountError::InvalidLogRecord.into()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(records: &[LogRecord]) -> Vec<u8> {
+        let mut data = vec![];
+        for record in records {
+            data.extend_from_slice(&record.header().unwrap());
+            data.extend_from_slice(record.data);
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_records_in_append_order() {
+        let records = [
+            LogRecord {
+                appender: Pubkey::new_unique(),
+                slot: 7,
+                data: b"hello",
+            },
+            LogRecord {
+                appender: Pubkey::new_unique(),
+                slot: 9,
+                data: b"",
+            },
+            LogRecord {
+                appender: Pubkey::new_unique(),
+                slot: 12,
+                data: b"world!",
+            },
+        ];
+        let data = encode(&records);
+        assert_eq!(
+            data.len(),
+            records.iter().map(LogRecord::size).sum::<usize>()
+        );
+
+        let decoded: Vec<LogRecord> = LogRecords::new(&data).map(Result::unwrap).collect();
+        assert_eq!(decoded, records);
+    }
+
+    #[test]
+    fn empty_log_has_no_records() {
+        assert_eq!(LogRecords::new(&[]).count(), 0);
+    }
+
+    #[test]
+    fn truncated_record_yields_error_and_stops() {
+        let record = LogRecord {
+            appender: Pubkey::new_unique(),
+            slot: 3,
+            data: b"record",
+        };
+        let data = encode(&[record.clone(), record.clone()]);
+
+        for len in [data.len() - 1, record.size() + LOG_RECORD_HEADER_SIZE - 1] {
+            let mut records = LogRecords::new(&data[..len]);
+            assert_eq!(records.next().unwrap().unwrap(), record);
+            assert_eq!(
+                records.next().unwrap().unwrap_err(),
+                ProgramError::from(DataAccountError::InvalidLogRecord)
+            );
+            assert!(records.next().is_none());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::io::{self, Read};
+
+use solana_program::{hash::hash, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{error::DataAccountError, state::Manifest};
+
+/// Streams the logical object described by a manifest by reading its shards in order
+///
+/// `fetch` is given a shard data account and should return its raw data. Every shard
+/// is checked against the length and SHA-256 hash recorded in the manifest before
+/// any of its bytes are returned
+pub struct ManifestReader<F> {
+    manifest: Manifest,
+    fetch: F,
+    shard_index: usize,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<F> ManifestReader<F>
+where
+    F: FnMut(&Pubkey) -> Result<Vec<u8>, ProgramError>,
+{
+    /// Default constructor
+    pub fn new(manifest: Manifest, fetch: F) -> Self {
+        ManifestReader {
+            manifest,
+            fetch,
+            shard_index: 0,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+    /// Get the manifest being read
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+    /// Fetches and verifies the next shard, returning false once all shards have been read
+    fn next_shard(&mut self) -> io::Result<bool> {
+        let shard = match self.manifest.shards.get(self.shard_index) {
+            Some(shard) => shard,
+            None => return Ok(false),
+        };
+
+        let data = (self.fetch)(&shard.account)
+            .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?;
+        if data.len() as u64 != shard.len || hash(&data).to_bytes() != shard.hash {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                DataAccountError::ShardMismatch,
+            ));
+        }
+
+        self.shard_index += 1;
+        self.buffer = data;
+        self.position = 0;
+        Ok(true)
+    }
+}
+
+impl<F> Read for ManifestReader<F>
+where
+    F: FnMut(&Pubkey) -> Result<Vec<u8>, ProgramError>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.buffer.len() {
+            if !self.next_shard()? {
+                return Ok(0);
+            }
+        }
+
+        let len = buf.len().min(self.buffer.len() - self.position);
+        buf[..len].copy_from_slice(&self.buffer[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::state::ShardEntry;
+
+    /// Builds a manifest of the shards along with a fetch over their data
+    fn manifest(
+        shards: &[&[u8]],
+    ) -> (
+        Manifest,
+        impl FnMut(&Pubkey) -> Result<Vec<u8>, ProgramError>,
+    ) {
+        let mut accounts = HashMap::new();
+        let mut manifest = Manifest::default();
+        for shard in shards {
+            let account = Pubkey::new_unique();
+            accounts.insert(account, shard.to_vec());
+            manifest.shards.push(ShardEntry {
+                account,
+                len: shard.len() as u64,
+                hash: hash(shard).to_bytes(),
+            });
+        }
+        let fetch = move |key: &Pubkey| {
+            accounts
+                .get(key)
+                .cloned()
+                .ok_or(ProgramError::UninitializedAccount)
+        };
+        (manifest, fetch)
+    }
+
+    #[test]
+    fn reads_shards_in_order() {
+        let (manifest, fetch) = manifest(&[b"hello ", b"", b"sharded ", b"world"]);
+        let mut reader = ManifestReader::new(manifest, fetch);
+
+        let mut object = Vec::new();
+        reader.read_to_end(&mut object).unwrap();
+        assert_eq!(object, b"hello sharded world");
+        assert_eq!(reader.manifest().total_len(), object.len() as u64);
+    }
+
+    #[test]
+    fn reads_across_shard_boundaries_with_small_buffers() {
+        let (manifest, fetch) = manifest(&[b"abc", b"defg", b"h"]);
+        let mut reader = ManifestReader::new(manifest, fetch);
+
+        let mut buf = [0; 2];
+        let mut object = Vec::new();
+        loop {
+            let len = reader.read(&mut buf).unwrap();
+            if len == 0 {
+                break;
+            }
+            object.extend_from_slice(&buf[..len]);
+        }
+        assert_eq!(object, b"abcdefgh");
+    }
+
+    #[test]
+    fn reads_nothing_from_empty_manifest() {
+        let (manifest, fetch) = manifest(&[]);
+        let mut reader = ManifestReader::new(manifest, fetch);
+
+        let mut object = Vec::new();
+        assert_eq!(reader.read_to_end(&mut object).unwrap(), 0);
+    }
+
+    #[test]
+    fn rejects_shard_with_wrong_length() {
+        let (mut manifest, fetch) = manifest(&[b"abc", b"def"]);
+        manifest.shards[1].len = 2;
+        let mut reader = ManifestReader::new(manifest, fetch);
+
+        let mut buf = [0; 3];
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+        let err = reader.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn rejects_shard_with_wrong_hash() {
+        let (mut manifest, fetch) = manifest(&[b"abc"]);
+        manifest.shards[0].hash = hash(b"abd").to_bytes();
+        let mut reader = ManifestReader::new(manifest, fetch);
+
+        let mut object = Vec::new();
+        let err = reader.read_to_end(&mut object).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(object.is_empty());
+    }
+
+    #[test]
+    fn propagates_fetch_errors() {
+        let (mut manifest, fetch) = manifest(&[b"abc"]);
+        manifest.shards[0].account = Pubkey::new_unique();
+        let mut reader = ManifestReader::new(manifest, fetch);
+
+        let mut buf = [0; 3];
+        let err = reader.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,218 @@
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::DataAccountError,
+    state::{DataAccountMetadata, DataTypeOption, Directory, PATH_SEPARATOR},
+};
+
+/// Maximum number of path components that are walked when resolving a path
+pub const MAX_PATH_DEPTH: usize = 32;
+
+/// Walks the path (e.g. `/site/img/logo.png`) starting from the root directory data account
+/// and returns the data account it points to along with its metadata
+///
+/// `fetch` is given a data account and should return its raw data and the raw data of
+/// its metadata PDA account, which allows this to be used both on and off-chain
+pub fn resolve_path<F>(
+    root: &Pubkey,
+    path: &str,
+    mut fetch: F,
+) -> Result<(Pubkey, DataAccountMetadata), ProgramError>
+where
+    F: FnMut(&Pubkey) -> Result<(Vec<u8>, Vec<u8>), ProgramError>,
+{
+    let components: Vec<&str> = path
+        .split(PATH_SEPARATOR)
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect();
+    if components.len() > MAX_PATH_DEPTH {
+        return Err(DataAccountError::InvalidPath.into());
+    }
+
+    let mut current = *root;
+    let (mut data, metadata) = fetch(&current)?;
+    let mut account_metadata = DataAccountMetadata::unpack(&metadata)?;
+
+    for component in components {
+        // ensure every component but the last one is a directory
+        if *account_metadata.data_type() != DataTypeOption::DIRECTORY {
+            return Err(DataAccountError::NotDirectory.into());
+        }
+        let directory = Directory::unpack(&data).map_err(|_| DataAccountError::NotDirectory)?;
+        let entry = directory
+            .entry(component)
+            .ok_or(DataAccountError::EntryNotFound)?;
+
+        current = entry.account;
+        let (next_data, next_metadata) = fetch(&current)?;
+        data = next_data;
+        account_metadata = DataAccountMetadata::unpack(&next_metadata)?;
+    }
+
+    Ok((current, account_metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use borsh::BorshSerialize;
+
+    use super::*;
+    use crate::state::{
+        DataStatusOption, DirectoryEntry, SerializationStatusOption, DATA_VERSION,
+        LEGACY_METADATA_SIZES,
+    };
+
+    /// Data accounts and their metadata keyed by data account
+    struct Tree(HashMap<Pubkey, (Vec<u8>, Vec<u8>)>);
+
+    impl Tree {
+        fn new() -> Self {
+            Tree(HashMap::new())
+        }
+        fn add(&mut self, data_type: DataTypeOption, data: Vec<u8>) -> Pubkey {
+            let key = Pubkey::new_unique();
+            let metadata = DataAccountMetadata::new(
+                DataStatusOption::INITIALIZED,
+                SerializationStatusOption::UNVERIFIED,
+                Pubkey::new_unique(),
+                true,
+                DATA_VERSION,
+                data_type,
+                255,
+            );
+            self.0.insert(key, (data, metadata.try_to_vec().unwrap()));
+            key
+        }
+        fn add_directory(&mut self, entries: &[(&str, Pubkey, bool)]) -> Pubkey {
+            let directory = Directory {
+                entries: entries
+                    .iter()
+                    .map(|(name, account, is_directory)| DirectoryEntry {
+                        name: name.to_string(),
+                        account: *account,
+                        is_directory: *is_directory,
+                    })
+                    .collect(),
+            };
+            self.add(DataTypeOption::DIRECTORY, directory.try_to_vec().unwrap())
+        }
+        fn resolve(&self, root: &Pubkey, path: &str) -> Result<Pubkey, ProgramError> {
+            resolve_path(root, path, |key| {
+                self.0
+                    .get(key)
+                    .cloned()
+                    .ok_or(ProgramError::UninitializedAccount)
+            })
+            .map(|(key, _)| key)
+        }
+    }
+
+    /// Builds `/site/index.html` and `/readme`, returning the root and both files
+    fn site() -> (Tree, Pubkey, Pubkey, Pubkey) {
+        let mut tree = Tree::new();
+        let index = tree.add(DataTypeOption::HTML, b"<html></html>".to_vec());
+        let readme = tree.add(DataTypeOption::CUSTOM, b"readme".to_vec());
+        let site = tree.add_directory(&[("index.html", index, false)]);
+        let root = tree.add_directory(&[("site", site, true), ("readme", readme, false)]);
+        (tree, root, index, readme)
+    }
+
+    #[test]
+    fn resolves_empty_path_to_root() {
+        let (tree, root, _, _) = site();
+        for path in ["", "/", ".", "/./"] {
+            assert_eq!(tree.resolve(&root, path), Ok(root));
+        }
+    }
+
+    #[test]
+    fn resolves_nested_path() {
+        let (tree, root, index, readme) = site();
+        assert_eq!(tree.resolve(&root, "/site/index.html"), Ok(index));
+        assert_eq!(tree.resolve(&root, "site//./index.html"), Ok(index));
+        assert_eq!(tree.resolve(&root, "/readme"), Ok(readme));
+    }
+
+    #[test]
+    fn returns_metadata_of_resolved_account() {
+        let (tree, root, _, _) = site();
+        let (_, metadata) = resolve_path(&root, "/site", |key| {
+            tree.0
+                .get(key)
+                .cloned()
+                .ok_or(ProgramError::UninitializedAccount)
+        })
+        .unwrap();
+        assert_eq!(*metadata.data_type(), DataTypeOption::DIRECTORY);
+    }
+
+    #[test]
+    fn resolves_through_legacy_metadata() {
+        for legacy_size in LEGACY_METADATA_SIZES {
+            let (mut tree, root, index, _) = site();
+            for (_, metadata) in tree.0.values_mut() {
+                metadata.truncate(legacy_size);
+            }
+
+            assert_eq!(tree.resolve(&root, "/site/index.html"), Ok(index));
+        }
+    }
+
+    #[test]
+    fn rejects_missing_entry() {
+        let (tree, root, _, _) = site();
+        for path in ["/missing", "/site/missing.html", "/site/.."] {
+            assert_eq!(
+                tree.resolve(&root, path),
+                Err(DataAccountError::EntryNotFound.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_walking_through_a_file() {
+        let (tree, root, _, _) = site();
+        assert_eq!(
+            tree.resolve(&root, "/readme/anything"),
+            Err(DataAccountError::NotDirectory.into())
+        );
+    }
+
+    #[test]
+    fn rejects_directory_type_with_invalid_data() {
+        let mut tree = Tree::new();
+        let root = tree.add(DataTypeOption::DIRECTORY, vec![0xff; 8]);
+        assert_eq!(
+            tree.resolve(&root, "/site"),
+            Err(DataAccountError::NotDirectory.into())
+        );
+    }
+
+    #[test]
+    fn rejects_paths_deeper_than_the_maximum() {
+        let mut tree = Tree::new();
+        let mut root = tree.add(DataTypeOption::CUSTOM, Vec::new());
+        for _ in 0..=MAX_PATH_DEPTH {
+            root = tree.add_directory(&[("a", root, true)]);
+        }
+
+        let path = "/a".repeat(MAX_PATH_DEPTH);
+        assert!(tree.resolve(&root, &path).is_ok());
+        assert_eq!(
+            tree.resolve(&root, &format!("{}/a", path)),
+            Err(DataAccountError::InvalidPath.into())
+        );
+    }
+
+    #[test]
+    fn propagates_fetch_errors() {
+        let mut tree = Tree::new();
+        let root = tree.add_directory(&[("dangling", Pubkey::new_unique(), false)]);
+        assert_eq!(
+            tree.resolve(&root, "/dangling"),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+}
@@ -1,24 +1,42 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint::ProgramResult,
+    bpf_loader_upgradeable, ed25519_program,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    hash::hash,
     msg,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
+    program_pack::Pack,
     pubkey::Pubkey,
-    system_instruction,
-    sysvar::{rent::Rent, Sysvar},
+    system_instruction, system_program,
+    sysvar::{
+        clock::Clock,
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+        rent::Rent,
+        Sysvar,
+    },
 };
 
 use crate::{
     error::DataAccountError,
     instruction::DataAccountInstruction,
+    log::{LogRecord, LOG_RECORD_HEADER_SIZE},
+    ring::{RingHeader, RING_HEADER_SIZE},
     state::{
-        DataAccountMetadata, DataStatusOption, DataTypeOption, SerializationStatusOption,
-        DATA_VERSION, METADATA_SIZE, PDA_SEED,
+        is_valid_entry_name, verify, AuthorityUsage, DataAccountMetadata, DataProgramConfig,
+        DataStatusOption, DataTypeOption, Delegation, Directory, DirectoryEntry, FrozenRange,
+        Manifest, RelayedUpdateMessage, RoleOption, SerializationStatusOption,
+        SessionInstructionOption, SessionKey, ShardEntry, UpdateDataAccountArgs, CONFIG_SEED,
+        CONFIG_SIZE, DATA_VERSION, DELEGATE_SIGNER_SEED, DELEGATION_SEED, DELEGATION_SIZE,
+        MAX_DELEGATE_RANGES, MAX_GUARDIANS, METADATA_SIZE, PDA_SEED, SESSION_SEED, SESSION_SIZE,
+        USAGE_SEED, USAGE_SIZE,
     },
 };
 
+/// The authority a data account is charged to with the bytes and data accounts it allocated
+type Usage = (Pubkey, u64, u64);
+
 pub struct Processor {}
 
 impl Processor {
@@ -30,6 +48,110 @@ impl Processor {
         let instruction = DataAccountInstruction::try_from_slice(instruction_data)
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
+        // ensure the instruction is allowed by the program config
+        let config = Self::check_config(program_id, accounts, &instruction)?;
+
+        // note the data accounts the instruction can resize, along with their metadata pdas, to
+        // check their final size and charge the change in their size to their authority
+        let resized_accounts = match &instruction {
+            DataAccountInstruction::InitializeDataAccount(_)
+            | DataAccountInstruction::UpdateDataAccount(_)
+            | DataAccountInstruction::CloseDataAccount(_)
+            | DataAccountInstruction::CreateDirectoryEntry(_)
+            | DataAccountInstruction::RenameDirectoryEntry(_)
+            | DataAccountInstruction::UnlinkDirectoryEntry(_)
+            | DataAccountInstruction::AppendShard(_)
+            | DataAccountInstruction::ReserveSpace(_)
+            | DataAccountInstruction::ResizeDataAccount(_)
+            | DataAccountInstruction::ReclaimExpired(_)
+            | DataAccountInstruction::RelayedUpdateDataAccount(_)
+            | DataAccountInstruction::DelegatedUpdateDataAccount(_)
+            | DataAccountInstruction::AppendRecord(_)
+            | DataAccountInstruction::InitializeRingBuffer(_) => &[(1, 2)][..],
+            DataAccountInstruction::MoveDirectoryEntry(_) => &[(1, 2), (3, 4)][..],
+            _ => &[][..],
+        };
+
+        // note what the authority had allocated before the instruction to track its usage,
+        // which is charged to the authority of the data account even if a role holder signs
+        let resized_accounts: Vec<(&AccountInfo, usize, Option<Usage>)> = resized_accounts
+            .iter()
+            .filter_map(|(data_index, metadata_index)| {
+                let data_account = accounts.get(*data_index)?;
+                let metadata_account = accounts.get(*metadata_index)?;
+                let usage = match &instruction {
+                    DataAccountInstruction::InitializeDataAccount(args) => {
+                        Some((args.authority, 0, 0))
+                    }
+                    _ => metadata_account
+                        .try_borrow_data()
+                        .ok()
+                        .and_then(|data| DataAccountMetadata::unpack(&data).ok())
+                        .map(|account_metadata| {
+                            (
+                                *account_metadata.authority(),
+                                data_account.data_len() as u64,
+                                1,
+                            )
+                        }),
+                };
+                Some((data_account, data_account.data_len(), usage))
+            })
+            .collect();
+
+        // records appended to a public log by anyone but a writer are charged without enforcing
+        // the quota, so that strangers cannot exhaust the quota of the authority
+        let enforce_quota = match (&instruction, accounts) {
+            (DataAccountInstruction::AppendRecord(_), [signer, _, metadata_account, ..]) => {
+                DataAccountMetadata::unpack(&metadata_account.try_borrow_data()?).map_or(
+                    true,
+                    |account_metadata| {
+                        !account_metadata.public_log()
+                            || account_metadata.has_role(RoleOption::WRITE, signer.key)
+                    },
+                )
+            }
+            _ => true,
+        };
+
+        Self::process(program_id, accounts, instruction)?;
+
+        for (data_account, old_len, usage) in resized_accounts {
+            // ensure no data account grew past the maximum account size
+            if let Some(config) = config
+                .as_ref()
+                .filter(|config| config.max_account_size() != 0)
+            {
+                if data_account.data_len() > old_len
+                    && data_account.data_len() as u64 > config.max_account_size()
+                {
+                    return Err(DataAccountError::AccountSizeLimitExceeded.into());
+                }
+            }
+
+            // ensure the authority stays within its usage quota
+            if let Some((authority, old_bytes, old_accounts)) = usage {
+                Self::track_usage(
+                    program_id,
+                    accounts,
+                    data_account,
+                    &authority,
+                    old_bytes,
+                    old_accounts,
+                    enforce_quota,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the instruction once the program config allows it
+    fn process(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction: DataAccountInstruction,
+    ) -> ProgramResult {
         match instruction {
             DataAccountInstruction::InitializeDataAccount(args) => {
                 if args.debug {
@@ -42,17 +164,47 @@ impl Processor {
                 let metadata_account = next_account_info(accounts_iter)?;
                 let system_program = next_account_info(accounts_iter)?;
 
+                // ensure feepayer and data_account are signers
+                if !feepayer.is_signer || !data_account.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure feepayer, data_account, and metadata_account are writable
+                if !feepayer.is_writable
+                    || !data_account.is_writable
+                    || !metadata_account.is_writable
+                {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                // ensure the pda is valid
+                let (pda, bump_seed) = Pubkey::find_program_address(
+                    &[PDA_SEED, data_account.key.as_ref()],
+                    program_id,
+                );
+                if pda != *metadata_account.key {
+                    return Err(DataAccountError::InvalidPDA.into());
+                }
+
+                // ensure the pda was not created previously
+                if metadata_account.owner == program_id {
+                    return Err(DataAccountError::AlreadyInitialized.into());
+                }
+
                 // create a data_account of given space if not done so already
                 if !args.is_created {
                     let space = args.space as usize;
                     let rent_exemption_amount = Rent::get()?.minimum_balance(space);
 
                     let create_account_ix = system_instruction::create_account(
-                        &feepayer.key,
-                        &data_account.key,
+                        feepayer.key,
+                        data_account.key,
                         rent_exemption_amount,
                         space as u64,
-                        &program_id,
+                        program_id,
                     );
                     invoke(
                         &create_account_ix,
@@ -69,7 +221,7 @@ impl Processor {
                 }
                 // else set data program as the owner of the data_account
                 else {
-                    let assign_ix = system_instruction::assign(&data_account.key, &program_id);
+                    let assign_ix = system_instruction::assign(data_account.key, program_id);
                     invoke(&assign_ix, &[data_account.clone(), system_program.clone()])?;
 
                     if args.debug {
@@ -79,22 +231,13 @@ impl Processor {
                 data_account.data.borrow_mut().fill(0);
 
                 // create data_account pda to store metadata
-                let (pda, bump_seed) = Pubkey::find_program_address(
-                    &[PDA_SEED, data_account.key.as_ref()],
-                    program_id,
-                );
-                // ensure the pda is valid
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
-                // create pda account
                 let rent_exemption_amount = Rent::get()?.minimum_balance(METADATA_SIZE);
                 let create_pda_ix = system_instruction::create_account(
-                    &feepayer.key,
-                    &metadata_account.key,
+                    feepayer.key,
+                    metadata_account.key,
                     rent_exemption_amount,
                     METADATA_SIZE as u64,
-                    &program_id,
+                    program_id,
                 );
                 invoke_signed(
                     &create_pda_ix,
@@ -121,7 +264,7 @@ impl Processor {
                     DataTypeOption::CUSTOM,
                     bump_seed,
                 );
-                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+                Self::store_metadata(&account_metadata, metadata_account)?;
 
                 Ok(())
             }
@@ -149,119 +292,61 @@ impl Processor {
                     return Err(DataAccountError::NotWriteable.into());
                 }
 
-                // ensure length is not 0
-                if metadata_account.data_is_empty() {
-                    return Err(DataAccountError::NoAccountLength.into());
-                }
-
-                let mut account_metadata =
-                    DataAccountMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
-
-                // ensure data_account is initialized and not finalized
-                match *account_metadata.data_status() {
-                    DataStatusOption::UNINITIALIZED => {
-                        return Err(DataAccountError::NotInitialized.into());
-                    }
-                    DataStatusOption::FINALIZED => {
-                        return Err(DataAccountError::AlreadyFinalized.into());
-                    }
-                    _ => (),
-                }
-
-                // ensure data_account is being written to by valid authority
-                if account_metadata.authority() != authority.key {
-                    return Err(DataAccountError::InvalidAuthority.into());
-                }
-
-                // ensure the metadata_account corresponds to the data_account
-                let pda = Pubkey::create_program_address(
-                    &[
-                        PDA_SEED,
-                        data_account.key.as_ref(),
-                        &[account_metadata.bump_seed()],
-                    ],
-                    program_id,
-                )?;
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
 
-                let old_len = data_account.data_len();
-                let end_len = args.offset as usize + args.data.len();
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
 
-                // ensure static data_account has sufficient space
-                if !account_metadata.dynamic() && old_len < end_len {
-                    return Err(DataAccountError::InsufficientSpace.into());
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
                 }
 
-                if args.debug {
-                    msg!("account checks passed");
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
                 }
 
-                let new_len = if !account_metadata.dynamic() {
-                    old_len
-                } else if args.realloc_down {
-                    end_len
+                // ensure data_account is being written to by the write authority or its session key,
+                // charging the session for the bytes written and the bytes truncated
+                let truncated = if account_metadata.dynamic() && args.realloc_down {
+                    (data_account.data_len() as u64)
+                        .saturating_sub(args.offset.saturating_add(args.data.len() as u64))
                 } else {
-                    old_len.max(end_len)
+                    0
                 };
+                let is_token_holder = Self::check_writer(
+                    program_id,
+                    accounts,
+                    authority,
+                    data_account,
+                    &account_metadata,
+                    SessionInstructionOption::UPDATE,
+                    (args.data.len() as u64).saturating_add(truncated),
+                )?;
 
-                // update the metadata_account
-                account_metadata.set_data_type(args.data_type);
-                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
-
-                // ensure data_account has enough space by reallocing if needed
-                if old_len != new_len {
-                    let new_space = new_len;
-                    let new_minimum_balance = Rent::get()?.minimum_balance(new_space);
-                    let lamports_diff = if old_len < new_len {
-                        new_minimum_balance.saturating_sub(data_account.lamports())
-                    } else {
-                        data_account.lamports().saturating_sub(new_minimum_balance)
-                    };
-
-                    if old_len < new_len {
-                        let transfer_ix = system_instruction::transfer(
-                            authority.key,
-                            data_account.key,
-                            lamports_diff,
-                        );
-                        invoke(
-                            &transfer_ix,
-                            &[
-                                authority.clone(),
-                                data_account.clone(),
-                                system_program.clone(),
-                            ],
-                        )?;
-                    } else {
-                        let authority_lamports = authority.lamports();
-                        **authority.lamports.borrow_mut() = authority_lamports
-                            .checked_add(lamports_diff)
-                            .ok_or(DataAccountError::Overflow)?;
-                        **data_account.lamports.borrow_mut() = new_minimum_balance;
-                    }
-
-                    data_account.realloc(new_space, false)?;
-
-                    if args.debug {
-                        msg!("realloc-ed {}", new_space);
+                // ensure a token holder only overwrites data in place, keeping the data_type
+                if is_token_holder {
+                    let old_len = data_account.data_len() as u64;
+                    let end_len = args.offset.saturating_add(args.data.len() as u64);
+                    if end_len > old_len
+                        || (account_metadata.dynamic() && args.realloc_down && end_len < old_len)
+                        || args.data_type != *account_metadata.data_type()
+                    {
+                        return Err(DataAccountError::TokenGateInPlace.into());
                     }
                 }
 
-                // update the data_account
-                if args.debug {
-                    msg!(
-                        "replaced {:?} with {:?}",
-                        &args.data,
-                        &data_account.data.borrow()[args.offset as usize..end_len]
-                    );
-                }
-
-                data_account.data.borrow_mut()[args.offset as usize..end_len]
-                    .copy_from_slice(&args.data);
-
-                Ok(())
+                Self::write_data(
+                    authority,
+                    Self::refund_account(accounts, &account_metadata),
+                    data_account,
+                    metadata_account,
+                    system_program,
+                    account_metadata,
+                    args,
+                )
             }
             DataAccountInstruction::UpdateDataAccountAuthority(args) => {
                 if args.debug {
@@ -284,44 +369,39 @@ impl Processor {
                     return Err(DataAccountError::NotWriteable.into());
                 }
 
-                // ensure length is not 0
-                if metadata_account.data_is_empty() {
-                    return Err(DataAccountError::NoAccountLength.into());
-                }
-
                 let mut account_metadata =
-                    DataAccountMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
-
-                // ensure data_account is initialized
-                if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
-                    return Err(DataAccountError::NotInitialized.into());
-                }
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
 
-                // ensure data_account is being written to by valid authority
-                if account_metadata.authority() != authority.key {
-                    return Err(DataAccountError::InvalidAuthority.into());
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
                 }
 
-                // ensure the metadata_account corresponds to the data_account
-                let pda = Pubkey::create_program_address(
-                    &[
-                        PDA_SEED,
-                        data_account.key.as_ref(),
-                        &[account_metadata.bump_seed()],
-                    ],
-                    program_id,
+                // ensure data_account is being written to by the authority manager
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::MANAGER,
+                    authority.key,
+                    accounts,
                 )?;
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
 
                 if args.debug {
                     msg!("account checks passed")
                 }
 
+                // move the usage of the data_account to the new authority
+                Self::move_usage(
+                    program_id,
+                    accounts,
+                    authority,
+                    data_account,
+                    account_metadata.authority(),
+                    new_authority.key,
+                )?;
+
                 // update the authority
                 account_metadata.set_authority(*new_authority.key);
-                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+                Self::store_metadata(&account_metadata, metadata_account)?;
 
                 if args.debug {
                     msg!("updated authority");
@@ -349,42 +429,26 @@ impl Processor {
                     return Err(DataAccountError::NotWriteable.into());
                 }
 
-                // ensure length is not 0
-                if metadata_account.data_is_empty() {
-                    return Err(DataAccountError::NoAccountLength.into());
-                }
-
                 let mut account_metadata =
-                    DataAccountMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
 
-                // ensure data_account is initialized and not finalized
-                match *account_metadata.data_status() {
-                    DataStatusOption::UNINITIALIZED => {
-                        return Err(DataAccountError::NotInitialized.into());
-                    }
-                    DataStatusOption::FINALIZED => {
-                        return Err(DataAccountError::AlreadyFinalized.into());
-                    }
-                    _ => (),
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
                 }
 
-                // ensure metadata_account is being written to by valid authority
-                if account_metadata.authority() != authority.key {
-                    return Err(DataAccountError::InvalidAuthority.into());
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
                 }
 
-                // ensure the metadata_account corresponds to the data_account
-                let pda = Pubkey::create_program_address(
-                    &[
-                        PDA_SEED,
-                        data_account.key.as_ref(),
-                        &[account_metadata.bump_seed()],
-                    ],
-                    program_id,
+                // ensure metadata_account is being written to by the finalize authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::FINALIZE,
+                    authority.key,
+                    accounts,
                 )?;
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
 
                 if args.debug {
                     msg!("account checks passed");
@@ -392,7 +456,7 @@ impl Processor {
 
                 // update the data_account
                 account_metadata.set_data_status(DataStatusOption::FINALIZED);
-                account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+                Self::store_metadata(&account_metadata, metadata_account)?;
 
                 if args.debug {
                     msg!("updated finalize flag");
@@ -423,36 +487,26 @@ impl Processor {
                     return Err(DataAccountError::NotWriteable.into());
                 }
 
-                // ensure length is not 0
-                if metadata_account.data_is_empty() {
-                    return Err(DataAccountError::NoAccountLength.into());
-                }
-
                 let account_metadata =
-                    DataAccountMetadata::try_from_slice(&metadata_account.try_borrow_data()?)?;
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
 
-                // ensure data_account is initialized
-                if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
-                    return Err(DataAccountError::NotInitialized.into());
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
                 }
 
-                // ensure data_account is being closed by valid authority
-                if account_metadata.authority() != authority.key {
-                    return Err(DataAccountError::InvalidAuthority.into());
+                // ensure data_account is not an append-only log
+                if *account_metadata.data_type() == DataTypeOption::LOG {
+                    return Err(DataAccountError::AppendOnly.into());
                 }
 
-                // ensure the metadata_account corresponds to the data_account
-                let pda = Pubkey::create_program_address(
-                    &[
-                        PDA_SEED,
-                        data_account.key.as_ref(),
-                        &[account_metadata.bump_seed()],
-                    ],
-                    program_id,
+                // ensure data_account is being closed by the close authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::CLOSE,
+                    authority.key,
+                    accounts,
                 )?;
-                if pda != *metadata_account.key {
-                    return Err(DataAccountError::InvalidPDA.into());
-                }
 
                 // transfer metadata_account lamports back to authority and reset metadata_account
                 let curr_lamports = authority.lamports();
@@ -480,6 +534,4285 @@ impl Processor {
 
                 Ok(())
             }
+            DataAccountInstruction::CreateDirectoryEntry(args) => {
+                if args.debug {
+                    msg!("CreateDirectoryEntry");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let directory_account = next_account_info(accounts_iter)?;
+                let directory_metadata_account = next_account_info(accounts_iter)?;
+                let entry_account = next_account_info(accounts_iter)?;
+                let entry_metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority is writable
+                if !authority.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                // ensure entry name is valid
+                if !is_valid_entry_name(&args.name) {
+                    return Err(DataAccountError::InvalidEntryName.into());
+                }
+
+                let (directory_metadata, mut directory) = Self::load_directory(
+                    program_id,
+                    authority,
+                    accounts,
+                    directory_account,
+                    directory_metadata_account,
+                )?;
+
+                let entry_metadata =
+                    Self::load_metadata(program_id, entry_account, entry_metadata_account)?;
+
+                // ensure entry name is not taken
+                if directory.entry(&args.name).is_some() {
+                    return Err(DataAccountError::EntryAlreadyExists.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // add the entry and write the directory back
+                directory.entries.push(DirectoryEntry {
+                    name: args.name,
+                    account: *entry_account.key,
+                    is_directory: *entry_metadata.data_type() == DataTypeOption::DIRECTORY,
+                });
+                Self::store_data(
+                    &directory,
+                    &directory_metadata,
+                    accounts,
+                    authority,
+                    directory_account,
+                    system_program,
+                )?;
+
+                if args.debug {
+                    msg!("created entry pointing to {}", entry_account.key);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::RenameDirectoryEntry(args) => {
+                if args.debug {
+                    msg!("RenameDirectoryEntry");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let directory_account = next_account_info(accounts_iter)?;
+                let directory_metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority is writable
+                if !authority.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                // ensure new entry name is valid
+                if !is_valid_entry_name(&args.new_name) {
+                    return Err(DataAccountError::InvalidEntryName.into());
+                }
+
+                let (directory_metadata, mut directory) = Self::load_directory(
+                    program_id,
+                    authority,
+                    accounts,
+                    directory_account,
+                    directory_metadata_account,
+                )?;
+
+                // ensure entry exists and new entry name is not taken
+                let position = directory
+                    .position(&args.name)
+                    .ok_or(DataAccountError::EntryNotFound)?;
+                if directory.entry(&args.new_name).is_some() {
+                    return Err(DataAccountError::EntryAlreadyExists.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // rename the entry and write the directory back
+                directory.entries[position].name = args.new_name;
+                Self::store_data(
+                    &directory,
+                    &directory_metadata,
+                    accounts,
+                    authority,
+                    directory_account,
+                    system_program,
+                )?;
+
+                if args.debug {
+                    msg!("renamed entry {}", args.name);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::MoveDirectoryEntry(args) => {
+                if args.debug {
+                    msg!("MoveDirectoryEntry");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let source_account = next_account_info(accounts_iter)?;
+                let source_metadata_account = next_account_info(accounts_iter)?;
+                let destination_account = next_account_info(accounts_iter)?;
+                let destination_metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority is writable
+                if !authority.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                // ensure source and destination are different directories
+                if source_account.key == destination_account.key {
+                    return Err(DataAccountError::InvalidPath.into());
+                }
+
+                let (source_metadata, mut source) = Self::load_directory(
+                    program_id,
+                    authority,
+                    accounts,
+                    source_account,
+                    source_metadata_account,
+                )?;
+                let (destination_metadata, mut destination) = Self::load_directory(
+                    program_id,
+                    authority,
+                    accounts,
+                    destination_account,
+                    destination_metadata_account,
+                )?;
+
+                // ensure entry exists in source and is not taken in destination
+                let position = source
+                    .position(&args.name)
+                    .ok_or(DataAccountError::EntryNotFound)?;
+                if destination.entry(&args.name).is_some() {
+                    return Err(DataAccountError::EntryAlreadyExists.into());
+                }
+
+                // ensure a directory is not moved into itself
+                if source.entries[position].account == *destination_account.key {
+                    return Err(DataAccountError::InvalidPath.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // move the entry and write both directories back
+                destination.entries.push(source.entries.remove(position));
+                Self::store_data(
+                    &source,
+                    &source_metadata,
+                    accounts,
+                    authority,
+                    source_account,
+                    system_program,
+                )?;
+                Self::store_data(
+                    &destination,
+                    &destination_metadata,
+                    accounts,
+                    authority,
+                    destination_account,
+                    system_program,
+                )?;
+
+                if args.debug {
+                    msg!("moved entry {}", args.name);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::UnlinkDirectoryEntry(args) => {
+                if args.debug {
+                    msg!("UnlinkDirectoryEntry");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let directory_account = next_account_info(accounts_iter)?;
+                let directory_metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority is writable
+                if !authority.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let (directory_metadata, mut directory) = Self::load_directory(
+                    program_id,
+                    authority,
+                    accounts,
+                    directory_account,
+                    directory_metadata_account,
+                )?;
+
+                // ensure entry exists
+                let position = directory
+                    .position(&args.name)
+                    .ok_or(DataAccountError::EntryNotFound)?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // remove the entry and write the directory back
+                directory.entries.remove(position);
+                Self::store_data(
+                    &directory,
+                    &directory_metadata,
+                    accounts,
+                    authority,
+                    directory_account,
+                    system_program,
+                )?;
+
+                if args.debug {
+                    msg!("unlinked entry {}", args.name);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::AppendShard(args) => {
+                if args.debug {
+                    msg!("AppendShard");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let manifest_account = next_account_info(accounts_iter)?;
+                let manifest_metadata_account = next_account_info(accounts_iter)?;
+                let shard_account = next_account_info(accounts_iter)?;
+                let shard_metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority is writable
+                if !authority.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let (manifest_metadata, mut manifest) = Self::load_manifest(
+                    program_id,
+                    authority,
+                    accounts,
+                    manifest_account,
+                    manifest_metadata_account,
+                )?;
+
+                let shard_metadata =
+                    Self::load_metadata(program_id, shard_account, shard_metadata_account)?;
+
+                // ensure shard has the same finalize authority so that it can be finalized later
+                if shard_metadata.role(RoleOption::FINALIZE)
+                    != manifest_metadata.role(RoleOption::FINALIZE)
+                {
+                    return Err(DataAccountError::InvalidAuthority.into());
+                }
+
+                // ensure shard can only be written to by the authority until it is finalized
+                Self::check_role(&shard_metadata, RoleOption::WRITE, authority.key, accounts)?;
+
+                // ensure shard is neither the manifest itself nor already appended
+                if shard_account.key == manifest_account.key
+                    || manifest
+                        .shards
+                        .iter()
+                        .any(|shard| shard.account == *shard_account.key)
+                {
+                    return Err(DataAccountError::DuplicateShard.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // append the shard and write the manifest back
+                manifest.shards.push(ShardEntry {
+                    account: *shard_account.key,
+                    len: shard_account.data_len() as u64,
+                    hash: args.hash,
+                });
+                Self::store_data(
+                    &manifest,
+                    &manifest_metadata,
+                    accounts,
+                    authority,
+                    manifest_account,
+                    system_program,
+                )?;
+
+                if args.debug {
+                    msg!(
+                        "appended shard {} of length {}",
+                        shard_account.key,
+                        shard_account.data_len()
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::FinalizeManifest(args) => {
+                if args.debug {
+                    msg!("FinalizeManifest");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let manifest_account = next_account_info(accounts_iter)?;
+                let manifest_metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure manifest_metadata_account is writable
+                if !manifest_metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut manifest_metadata =
+                    Self::load_metadata(program_id, manifest_account, manifest_metadata_account)?;
+
+                // ensure manifest is not finalized
+                if *manifest_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure manifest is not frozen by the arbiter
+                if manifest_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure manifest is being finalized by the finalize authority
+                Self::check_role(
+                    &manifest_metadata,
+                    RoleOption::FINALIZE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure manifest_account holds a manifest
+                if *manifest_metadata.data_type() != DataTypeOption::MANIFEST {
+                    return Err(DataAccountError::NotManifest.into());
+                }
+                let manifest = Manifest::unpack(&manifest_account.try_borrow_data()?)
+                    .map_err(|_| DataAccountError::NotManifest)?;
+
+                // finalize every shard in manifest order
+                for shard in manifest.shards.iter() {
+                    let shard_account = next_account_info(accounts_iter)?;
+                    let shard_metadata_account = next_account_info(accounts_iter)?;
+
+                    // ensure shard_metadata_account is writable
+                    if !shard_metadata_account.is_writable {
+                        return Err(DataAccountError::NotWriteable.into());
+                    }
+
+                    let mut shard_metadata =
+                        Self::load_metadata(program_id, shard_account, shard_metadata_account)?;
+
+                    // ensure shard is being finalized by the finalize authority
+                    Self::check_role(
+                        &shard_metadata,
+                        RoleOption::FINALIZE,
+                        authority.key,
+                        accounts,
+                    )?;
+
+                    // ensure shard still holds the data it was appended with
+                    if shard.account != *shard_account.key
+                        || shard.len != shard_account.data_len() as u64
+                        || hash(&shard_account.try_borrow_data()?).to_bytes() != shard.hash
+                    {
+                        return Err(DataAccountError::ShardMismatch.into());
+                    }
+
+                    shard_metadata.set_data_status(DataStatusOption::FINALIZED);
+                    Self::store_metadata(&shard_metadata, shard_metadata_account)?;
+
+                    if args.debug {
+                        msg!("finalized shard {}", shard_account.key);
+                    }
+                }
+
+                // finalize the manifest
+                manifest_metadata.set_data_status(DataStatusOption::FINALIZED);
+                Self::store_metadata(&manifest_metadata, manifest_metadata_account)?;
+
+                if args.debug {
+                    msg!(
+                        "finalized manifest of {} shards and length {}",
+                        manifest.shards.len(),
+                        manifest.total_len()
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::ReserveSpace(args) => {
+                if args.debug {
+                    msg!("ReserveSpace");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority and data_account are writable
+                if !authority.is_writable || !data_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is not an append-only log
+                if *account_metadata.data_type() == DataTypeOption::LOG {
+                    return Err(DataAccountError::AppendOnly.into());
+                }
+
+                // ensure data_account is not a ring buffer
+                if *account_metadata.data_type() == DataTypeOption::RING {
+                    return Err(DataAccountError::RingBuffer.into());
+                }
+
+                // grow towards the requested space by at most the realloc limit
+                let old_len = data_account.data_len();
+                let new_len = (args.space as usize).min(old_len + MAX_PERMITTED_DATA_INCREASE);
+
+                // ensure data_account is being resized by the write authority or its session key
+                Self::check_writer(
+                    program_id,
+                    accounts,
+                    authority,
+                    data_account,
+                    &account_metadata,
+                    SessionInstructionOption::RESERVE,
+                    new_len.saturating_sub(old_len) as u64,
+                )?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                if new_len > old_len {
+                    Self::resize_data_account(
+                        authority,
+                        None,
+                        data_account,
+                        system_program,
+                        new_len,
+                    )?;
+                }
+
+                if args.debug {
+                    msg!("reserved {} of {}", data_account.data_len(), args.space);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::ResizeDataAccount(args) => {
+                if args.debug {
+                    msg!("ResizeDataAccount");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority and data_account are writable
+                if !authority.is_writable || !data_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is not an append-only log
+                if *account_metadata.data_type() == DataTypeOption::LOG {
+                    return Err(DataAccountError::AppendOnly.into());
+                }
+
+                // ensure data_account is not a ring buffer
+                if *account_metadata.data_type() == DataTypeOption::RING {
+                    return Err(DataAccountError::RingBuffer.into());
+                }
+
+                let old_len = data_account.data_len();
+                let new_len = args.len as usize;
+
+                // ensure data_account is being resized by the write authority or its session key
+                Self::check_writer(
+                    program_id,
+                    accounts,
+                    authority,
+                    data_account,
+                    &account_metadata,
+                    SessionInstructionOption::RESIZE,
+                    new_len.abs_diff(old_len) as u64,
+                )?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // ensure no frozen data is truncated
+                if account_metadata.is_frozen(args.len, old_len as u64) {
+                    return Err(DataAccountError::RangeFrozen.into());
+                }
+
+                if old_len != new_len {
+                    Self::resize_data_account(
+                        authority,
+                        Self::refund_account(accounts, &account_metadata),
+                        data_account,
+                        system_program,
+                        new_len,
+                    )?;
+                }
+
+                if args.debug {
+                    msg!("resized from {} to {}", old_len, new_len);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::SetDynamic(args) => {
+                if args.debug {
+                    msg!("SetDynamic");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is being updated by the write authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the dynamic flag
+                account_metadata.set_dynamic(args.is_dynamic);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("updated dynamic flag to {}", args.is_dynamic);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::WithdrawExcessLamports(args) => {
+                if args.debug {
+                    msg!("WithdrawExcessLamports");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let recipient = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure data_account, metadata_account and recipient are writable
+                if !data_account.is_writable
+                    || !metadata_account.is_writable
+                    || !recipient.is_writable
+                {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure lamports are being withdrawn by the close authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::CLOSE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // transfer everything above the rent exemption of both accounts to recipient
+                let rent = Rent::get()?;
+                for account in [data_account, metadata_account] {
+                    let excess_lamports = account
+                        .lamports()
+                        .saturating_sub(rent.minimum_balance(account.data_len()));
+                    if excess_lamports == 0 {
+                        continue;
+                    }
+
+                    let recipient_lamports = recipient.lamports();
+                    **recipient.lamports.borrow_mut() = recipient_lamports
+                        .checked_add(excess_lamports)
+                        .ok_or(DataAccountError::Overflow)?;
+                    let account_lamports = account.lamports();
+                    **account.lamports.borrow_mut() = account_lamports - excess_lamports;
+
+                    if args.debug {
+                        msg!("{} withdrawn from {}", excess_lamports, account.key);
+                    }
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::TopUpRent(args) => {
+                if args.debug {
+                    msg!("TopUpRent");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let payer = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure payer is signer
+                if !payer.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure payer, data_account and metadata_account are writable
+                if !payer.is_writable || !data_account.is_writable || !metadata_account.is_writable
+                {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // transfer whatever is missing from the rent exemption of both accounts
+                let rent = Rent::get()?;
+                for account in [data_account, metadata_account] {
+                    let missing_lamports = rent
+                        .minimum_balance(account.data_len())
+                        .saturating_sub(account.lamports());
+                    if missing_lamports == 0 {
+                        continue;
+                    }
+
+                    let transfer_ix =
+                        system_instruction::transfer(payer.key, account.key, missing_lamports);
+                    invoke(
+                        &transfer_ix,
+                        &[payer.clone(), account.clone(), system_program.clone()],
+                    )?;
+
+                    if args.debug {
+                        msg!("{} topped up for {}", missing_lamports, account.key);
+                    }
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::InitializeConfig(args) => {
+                if args.debug {
+                    msg!("InitializeConfig");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let upgrade_authority = next_account_info(accounts_iter)?;
+                let config_account = next_account_info(accounts_iter)?;
+                let program_data = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure upgrade_authority is signer
+                if !upgrade_authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure upgrade_authority and config_account are writable
+                if !upgrade_authority.is_writable || !config_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                // ensure upgrade_authority is the upgrade authority of the data program
+                let (program_data_address, _) = Pubkey::find_program_address(
+                    &[program_id.as_ref()],
+                    &bpf_loader_upgradeable::id(),
+                );
+                if program_data_address != *program_data.key
+                    || !bpf_loader_upgradeable::check_id(program_data.owner)
+                {
+                    return Err(DataAccountError::InvalidAdmin.into());
+                }
+                {
+                    // ProgramData is a u32 tag of 3, a u64 slot and an optional upgrade authority
+                    let data = program_data.try_borrow_data()?;
+                    if data.len() < 45
+                        || data[..4] != [3, 0, 0, 0]
+                        || data[12] != 1
+                        || data[13..45] != upgrade_authority.key.to_bytes()
+                    {
+                        return Err(DataAccountError::InvalidAdmin.into());
+                    }
+                }
+
+                // ensure the config pda is valid
+                let (pda, bump_seed) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+                if pda != *config_account.key {
+                    return Err(DataAccountError::InvalidConfig.into());
+                }
+
+                // ensure the config pda was not created previously
+                if config_account.owner == program_id {
+                    return Err(DataAccountError::AlreadyInitialized.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // create the config pda
+                let rent_exemption_amount = Rent::get()?.minimum_balance(CONFIG_SIZE);
+                let create_pda_ix = system_instruction::create_account(
+                    upgrade_authority.key,
+                    config_account.key,
+                    rent_exemption_amount,
+                    CONFIG_SIZE as u64,
+                    program_id,
+                );
+                invoke_signed(
+                    &create_pda_ix,
+                    &[
+                        upgrade_authority.clone(),
+                        config_account.clone(),
+                        system_program.clone(),
+                    ],
+                    &[&[CONFIG_SEED, &[bump_seed]]],
+                )?;
+
+                let config = DataProgramConfig::new(args.admin, bump_seed);
+                config.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+                if args.debug {
+                    msg!("config pda created with admin {}", args.admin);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::UpdateConfig(args) => {
+                if args.debug {
+                    msg!("UpdateConfig");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let admin = next_account_info(accounts_iter)?;
+                let config_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure admin is signer
+                if !admin.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure admin and config_account are writable
+                if !admin.is_writable || !config_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let mut config = Self::load_config(program_id, config_account)?;
+
+                // ensure config is being updated by valid admin
+                if config.admin() != admin.key {
+                    return Err(DataAccountError::InvalidAdmin.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the config
+                config.set_admin(args.admin);
+                config.set_paused(args.is_paused);
+                config.set_max_account_size(args.max_account_size);
+                config.set_max_write_size(args.max_write_size);
+                config.set_protocol_fee(args.protocol_fee);
+                config.set_treasury(args.treasury);
+                config.set_storage_fee_per_byte(args.storage_fee_per_byte);
+                config.set_fee_exempt(args.fee_exempt);
+
+                // realloc the config_account to fit the fee exemption list
+                let serialized = config.try_to_vec()?;
+                if config_account.data_len() != serialized.len() {
+                    Self::resize_data_account(
+                        admin,
+                        Some(admin),
+                        config_account,
+                        system_program,
+                        serialized.len(),
+                    )?;
+                }
+                config_account
+                    .data
+                    .borrow_mut()
+                    .copy_from_slice(&serialized);
+
+                if args.debug {
+                    msg!("updated config");
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::WithdrawProtocolFees(args) => {
+                if args.debug {
+                    msg!("WithdrawProtocolFees");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let admin = next_account_info(accounts_iter)?;
+                let config_account = next_account_info(accounts_iter)?;
+                let treasury = next_account_info(accounts_iter)?;
+
+                // ensure admin is signer
+                if !admin.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure config_account and treasury are writable
+                if !config_account.is_writable || !treasury.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let config = Self::load_config(program_id, config_account)?;
+
+                // ensure fees are being withdrawn by valid admin
+                if config.admin() != admin.key {
+                    return Err(DataAccountError::InvalidAdmin.into());
+                }
+
+                // ensure fees are being withdrawn to the treasury
+                if config.treasury() != treasury.key {
+                    return Err(DataAccountError::InvalidTreasury.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // transfer everything above the rent exemption of the config pda to treasury
+                let fees = config_account
+                    .lamports()
+                    .saturating_sub(Rent::get()?.minimum_balance(config_account.data_len()));
+                let treasury_lamports = treasury.lamports();
+                **treasury.lamports.borrow_mut() = treasury_lamports
+                    .checked_add(fees)
+                    .ok_or(DataAccountError::Overflow)?;
+                let config_lamports = config_account.lamports();
+                **config_account.lamports.borrow_mut() = config_lamports - fees;
+
+                if args.debug {
+                    msg!("{} withdrawn to treasury", fees);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::SetUsageQuota(args) => {
+                if args.debug {
+                    msg!("SetUsageQuota");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let admin = next_account_info(accounts_iter)?;
+                let config_account = next_account_info(accounts_iter)?;
+                let authority = next_account_info(accounts_iter)?;
+                let usage_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure admin is signer
+                if !admin.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure admin and usage_account are writable
+                if !admin.is_writable || !usage_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let config = Self::load_config(program_id, config_account)?;
+
+                // ensure quota is being set by valid admin
+                if config.admin() != admin.key {
+                    return Err(DataAccountError::InvalidAdmin.into());
+                }
+
+                // ensure the usage pda is valid
+                let (pda, bump_seed) =
+                    Pubkey::find_program_address(&[USAGE_SEED, authority.key.as_ref()], program_id);
+                if pda != *usage_account.key {
+                    return Err(DataAccountError::InvalidUsage.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                let mut usage = if usage_account.owner == program_id {
+                    AuthorityUsage::try_from_slice(&usage_account.try_borrow_data()?)?
+                } else {
+                    Self::create_usage_account(
+                        program_id,
+                        accounts,
+                        admin,
+                        usage_account,
+                        authority.key,
+                        bump_seed,
+                    )?;
+
+                    if args.debug {
+                        msg!("usage pda created");
+                    }
+
+                    AuthorityUsage::new(*authority.key, bump_seed)
+                };
+
+                // update the quota
+                usage.set_max_bytes(args.max_bytes);
+                usage.set_max_accounts(args.max_accounts);
+                usage.serialize(&mut &mut usage_account.data.borrow_mut()[..])?;
+
+                if args.debug {
+                    msg!(
+                        "quota set to {} bytes and {} accounts",
+                        args.max_bytes,
+                        args.max_accounts
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::SetExpiry(args) => {
+                if args.debug {
+                    msg!("SetExpiry");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure expiry is being set by the close authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::CLOSE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure an expiring nft-bound data_account is not reclaimed to its mint
+                if args.expires_at_slot != 0
+                    && args.beneficiary == Pubkey::default()
+                    && account_metadata.nft_mint().is_some()
+                {
+                    return Err(DataAccountError::InvalidBeneficiary.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the expiry and beneficiary
+                account_metadata
+                    .set_expires_at_slot(Some(args.expires_at_slot).filter(|slot| *slot != 0));
+                account_metadata.set_beneficiary(
+                    Some(args.beneficiary).filter(|beneficiary| *beneficiary != Pubkey::default()),
+                );
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!(
+                        "expires at slot {} with beneficiary {:?}",
+                        args.expires_at_slot,
+                        account_metadata.beneficiary()
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::ReclaimExpired(args) => {
+                if args.debug {
+                    msg!("ReclaimExpired");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let beneficiary = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure beneficiary, data_account, and metadata_account are writable
+                if !beneficiary.is_writable
+                    || !data_account.is_writable
+                    || !metadata_account.is_writable
+                {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not an append-only log
+                if *account_metadata.data_type() == DataTypeOption::LOG {
+                    return Err(DataAccountError::AppendOnly.into());
+                }
+
+                // ensure the close role was not renounced
+                if account_metadata.renounced(RoleOption::CLOSE) {
+                    return Err(DataAccountError::RoleRenounced.into());
+                }
+
+                // ensure data_account has expired
+                match account_metadata.expires_at_slot() {
+                    Some(expires_at_slot) if Clock::get()?.slot >= expires_at_slot => {}
+                    _ => return Err(DataAccountError::NotExpired.into()),
+                }
+
+                // ensure lamports are returned to the beneficiary
+                if account_metadata.beneficiary() != Some(beneficiary.key) {
+                    return Err(DataAccountError::InvalidBeneficiary.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // transfer the lamports of both accounts to beneficiary and reset them
+                for account in [metadata_account, data_account] {
+                    let account_lamports = account.lamports();
+                    let beneficiary_lamports = beneficiary.lamports();
+                    **beneficiary.lamports.borrow_mut() = beneficiary_lamports
+                        .checked_add(account_lamports)
+                        .ok_or(DataAccountError::Overflow)?;
+                    **account.lamports.borrow_mut() = 0;
+                    account.data.borrow_mut().fill(0);
+
+                    if args.debug {
+                        msg!("{} reclaimed from {}", account_lamports, account.key);
+                    }
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::ScheduleFinalize(args) => {
+                if args.debug {
+                    msg!("ScheduleFinalize");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure finalization is being scheduled by the finalize authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::FINALIZE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure the scheduled finalization is only ever brought forward
+                let finalize_at = Some(args.finalize_at).filter(|at| *at != 0);
+                let finalize_at_slot = Some(args.finalize_at_slot).filter(|slot| *slot != 0);
+                if (finalize_at.is_none() && finalize_at_slot.is_none())
+                    || matches!(
+                        (finalize_at, account_metadata.finalize_at()),
+                        (Some(new), Some(old)) if new > old
+                    )
+                    || matches!(
+                        (finalize_at_slot, account_metadata.finalize_at_slot()),
+                        (Some(new), Some(old)) if new > old
+                    )
+                {
+                    return Err(DataAccountError::FinalizePostponed.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the scheduled finalization, keeping the deadline that is not passed
+                if finalize_at.is_some() {
+                    account_metadata.set_finalize_at(finalize_at);
+                }
+                if finalize_at_slot.is_some() {
+                    account_metadata.set_finalize_at_slot(finalize_at_slot);
+                }
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!(
+                        "finalize scheduled at {} or slot {}",
+                        account_metadata.finalize_at().unwrap_or(0),
+                        account_metadata.finalize_at_slot().unwrap_or(0)
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::FinalizeScheduled(args) => {
+                if args.debug {
+                    msg!("FinalizeScheduled");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure the scheduled finalization has passed
+                if !account_metadata.finalize_scheduled()
+                    || *account_metadata.data_status() != DataStatusOption::FINALIZED
+                {
+                    return Err(DataAccountError::FinalizeNotDue.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // persist the finalized data status
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("updated finalize flag");
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::FreezeRange(args) => {
+                if args.debug {
+                    msg!("FreezeRange");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure range is being frozen by the write authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure range is non-empty and within the data
+                if args.start >= args.end || args.end > data_account.data_len() as u64 {
+                    return Err(DataAccountError::InvalidFrozenRange.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // record the frozen range
+                let range = FrozenRange {
+                    start: args.start,
+                    end: args.end,
+                };
+                if !account_metadata.add_frozen_range(range) {
+                    return Err(DataAccountError::TooManyFrozenRanges.into());
+                }
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("froze [{}, {})", args.start, args.end);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::TransferRole(args) => {
+                if args.debug {
+                    msg!("TransferRole");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let holder = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let new_holder = next_account_info(accounts_iter)?;
+
+                // ensure holder and new_holder are signer
+                if !holder.is_signer || !new_holder.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure role has not been renounced
+                if account_metadata.renounced(args.role) {
+                    return Err(DataAccountError::RoleRenounced.into());
+                }
+
+                // ensure role is being transferred by its holder or the authority manager
+                Self::check_role(&account_metadata, args.role, holder.key, accounts).or_else(
+                    |_| {
+                        Self::check_role(
+                            &account_metadata,
+                            RoleOption::MANAGER,
+                            holder.key,
+                            accounts,
+                        )
+                    },
+                )?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the role holder
+                account_metadata.set_role(args.role, Some(*new_holder.key));
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("transferred {:?} role to {}", args.role, new_holder.key);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::RenounceRole(args) => {
+                if args.debug {
+                    msg!("RenounceRole");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let holder = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure holder is signer
+                if !holder.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure role has not been renounced
+                if account_metadata.renounced(args.role) {
+                    return Err(DataAccountError::RoleRenounced.into());
+                }
+
+                // ensure role is being renounced by its holder or the authority manager
+                Self::check_role(&account_metadata, args.role, holder.key, accounts).or_else(
+                    |_| {
+                        Self::check_role(
+                            &account_metadata,
+                            RoleOption::MANAGER,
+                            holder.key,
+                            accounts,
+                        )
+                    },
+                )?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // renounce the role for good
+                account_metadata.set_role(args.role, None);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("renounced {:?} role", args.role);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::RenounceAuthority(args) => {
+                if args.debug {
+                    msg!("RenounceAuthority");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure authority is being renounced by the authority manager
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::MANAGER,
+                    authority.key,
+                    accounts,
+                )?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // leave the data_account without an owner for good
+                account_metadata.renounce_authority();
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("renounced authority");
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::SetGuardians(args) => {
+                if args.debug {
+                    msg!("SetGuardians");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure guardians are being set by the authority manager
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::MANAGER,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure guardians are distinct, within the maximum and can reach the threshold
+                if args.guardians.len() > MAX_GUARDIANS
+                    || args.guardians.iter().enumerate().any(|(i, guardian)| {
+                        *guardian == Pubkey::default() || args.guardians[..i].contains(guardian)
+                    })
+                    || args.threshold as usize > args.guardians.len()
+                    || (args.threshold == 0 && !args.guardians.is_empty())
+                    || args.delay < 0
+                    || (args.delay == 0 && !args.guardians.is_empty())
+                {
+                    return Err(DataAccountError::InvalidGuardians.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the guardians
+                account_metadata.set_guardians(&args.guardians, args.threshold, args.delay);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!(
+                        "set {} of {} guardians with a delay of {}",
+                        args.threshold,
+                        args.guardians.len(),
+                        args.delay
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::InitiateRecovery(args) => {
+                if args.debug {
+                    msg!("InitiateRecovery");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let new_authority = next_account_info(accounts_iter)?;
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure the data account is not recovered to the default pubkey
+                if *new_authority.key == Pubkey::default() {
+                    return Err(DataAccountError::InvalidRecoveryAuthority.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure the authority can still be transferred
+                if account_metadata.renounced(RoleOption::MANAGER) {
+                    return Err(DataAccountError::RoleRenounced.into());
+                }
+
+                // ensure a quorum of distinct guardians signed, ignoring any other account passed
+                let guardians = &accounts[3..];
+                let signers = guardians
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, guardian)| {
+                        guardian.is_signer
+                            && account_metadata.is_guardian(guardian.key)
+                            && !guardians[..*i]
+                                .iter()
+                                .any(|other| other.key == guardian.key)
+                    })
+                    .count();
+                if account_metadata.guardian_threshold() == 0
+                    || signers < account_metadata.guardian_threshold() as usize
+                {
+                    return Err(DataAccountError::QuorumNotReached.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // start the recovery delay
+                let recover_at = Clock::get()?
+                    .unix_timestamp
+                    .checked_add(account_metadata.recovery_delay())
+                    .ok_or(DataAccountError::Overflow)?;
+                account_metadata.set_pending_recovery(Some((*new_authority.key, recover_at)));
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!(
+                        "recovery to {} can be completed at {}",
+                        new_authority.key,
+                        recover_at
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::CancelRecovery(args) => {
+                if args.debug {
+                    msg!("CancelRecovery");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure recovery is being cancelled by the authority or the authority manager
+                if account_metadata.authority() != authority.key
+                    && Self::check_role(
+                        &account_metadata,
+                        RoleOption::MANAGER,
+                        authority.key,
+                        accounts,
+                    )
+                    .is_err()
+                {
+                    return Err(DataAccountError::InvalidAuthority.into());
+                }
+
+                // ensure a recovery is pending
+                if account_metadata.pending_recovery().is_none() {
+                    return Err(DataAccountError::NoPendingRecovery.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // clear the pending recovery
+                account_metadata.set_pending_recovery(None);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("cancelled recovery");
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::CompleteRecovery(args) => {
+                if args.debug {
+                    msg!("CompleteRecovery");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let payer = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure payer is signer
+                if !payer.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure payer and metadata_account are writable
+                if !payer.is_writable || !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure the authority can still be transferred
+                if account_metadata.renounced(RoleOption::MANAGER) {
+                    return Err(DataAccountError::RoleRenounced.into());
+                }
+
+                // ensure a recovery is pending and its delay has passed
+                let (new_authority, recover_at) = account_metadata
+                    .pending_recovery()
+                    .map(|(new_authority, recover_at)| (*new_authority, recover_at))
+                    .ok_or(DataAccountError::NoPendingRecovery)?;
+                if Clock::get()?.unix_timestamp < recover_at {
+                    return Err(DataAccountError::RecoveryNotDue.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // move the usage of the data_account to the new authority
+                Self::move_usage(
+                    program_id,
+                    accounts,
+                    payer,
+                    data_account,
+                    account_metadata.authority(),
+                    &new_authority,
+                )?;
+
+                // rotate the authority
+                account_metadata.recover(new_authority);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("recovered authority to {}", new_authority);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::SetArbiter(args) => {
+                if args.debug {
+                    msg!("SetArbiter");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure a designated arbiter can only be replaced by itself
+                match account_metadata.arbiter() {
+                    Some(arbiter) if arbiter != authority.key => {
+                        return Err(DataAccountError::InvalidArbiter.into());
+                    }
+                    Some(_) => {}
+                    None => Self::check_role(
+                        &account_metadata,
+                        RoleOption::MANAGER,
+                        authority.key,
+                        accounts,
+                    )?,
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the arbiter
+                account_metadata.set_arbiter(
+                    Some(args.arbiter).filter(|arbiter| *arbiter != Pubkey::default()),
+                );
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("updated arbiter to {}", args.arbiter);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::FreezeDataAccount(args) => {
+                if args.debug {
+                    msg!("FreezeDataAccount");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let arbiter = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure arbiter is signer
+                if !arbiter.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is being frozen by its arbiter
+                if account_metadata.arbiter() != Some(arbiter.key) {
+                    return Err(DataAccountError::InvalidArbiter.into());
+                }
+
+                // ensure data_account is not frozen already
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the arbiter frozen flag
+                account_metadata.set_arbiter_frozen(true);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("froze data account");
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::ThawDataAccount(args) => {
+                if args.debug {
+                    msg!("ThawDataAccount");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let arbiter = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure arbiter is signer
+                if !arbiter.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is being thawed by its arbiter
+                if account_metadata.arbiter() != Some(arbiter.key) {
+                    return Err(DataAccountError::InvalidArbiter.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the arbiter frozen flag
+                account_metadata.set_arbiter_frozen(false);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("thawed data account");
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::RelayedUpdateDataAccount(args) => {
+                if args.debug {
+                    msg!("RelayedUpdateDataAccount");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let relayer = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+                let instructions_sysvar = next_account_info(accounts_iter)?;
+
+                // ensure relayer is signer
+                if !relayer.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure relayer, data_account, and metadata_account are writable
+                if !relayer.is_writable
+                    || !data_account.is_writable
+                    || !metadata_account.is_writable
+                {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure the update was signed by the write authority
+                let message = RelayedUpdateMessage::new(*data_account.key, &args).to_bytes()?;
+                let signer = Self::load_relay_signer(instructions_sysvar, &message)?;
+                Self::check_role(&account_metadata, RoleOption::WRITE, &signer, accounts)?;
+
+                // ensure the signed message is neither replayed nor expired
+                if args.nonce != account_metadata.relay_nonce() {
+                    return Err(DataAccountError::InvalidNonce.into());
+                }
+                if Clock::get()?.slot > args.expires_at_slot {
+                    return Err(DataAccountError::RelayExpired.into());
+                }
+
+                // consume the nonce
+                account_metadata.set_relay_nonce(
+                    args.nonce
+                        .checked_add(1)
+                        .ok_or(DataAccountError::Overflow)?,
+                );
+
+                if args.debug {
+                    msg!("relayed by {} for {}", relayer.key, signer);
+                }
+
+                Self::write_data(
+                    relayer,
+                    Self::refund_account(accounts, &account_metadata),
+                    data_account,
+                    metadata_account,
+                    system_program,
+                    account_metadata,
+                    UpdateDataAccountArgs {
+                        data_type: args.data_type,
+                        data: args.data,
+                        offset: args.offset,
+                        realloc_down: args.realloc_down,
+                        verify_flag: args.verify_flag,
+                        allow_sparse: args.allow_sparse,
+                        fill_byte: args.fill_byte,
+                        debug: args.debug,
+                    },
+                )
+            }
+            DataAccountInstruction::CreateSession(args) => {
+                if args.debug {
+                    msg!("CreateSession");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let session_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority and session_account are writable
+                if !authority.is_writable || !session_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure session key is being registered by the write authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure the session pda is valid
+                let (pda, bump_seed) = Pubkey::find_program_address(
+                    &[
+                        SESSION_SEED,
+                        data_account.key.as_ref(),
+                        args.session_key.as_ref(),
+                    ],
+                    program_id,
+                );
+                if pda != *session_account.key {
+                    return Err(DataAccountError::InvalidSession.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // create the session pda unless the session key is being registered again
+                if session_account.owner != program_id {
+                    let rent_exemption_amount = Rent::get()?.minimum_balance(SESSION_SIZE);
+                    let create_pda_ix = system_instruction::create_account(
+                        authority.key,
+                        session_account.key,
+                        rent_exemption_amount,
+                        SESSION_SIZE as u64,
+                        program_id,
+                    );
+                    invoke_signed(
+                        &create_pda_ix,
+                        &[
+                            authority.clone(),
+                            session_account.clone(),
+                            system_program.clone(),
+                        ],
+                        &[&[
+                            SESSION_SEED,
+                            data_account.key.as_ref(),
+                            args.session_key.as_ref(),
+                            &[bump_seed],
+                        ]],
+                    )?;
+
+                    if args.debug {
+                        msg!("session pda created");
+                    }
+                }
+
+                let session = SessionKey::new(
+                    *data_account.key,
+                    args.session_key,
+                    *authority.key,
+                    args.expires_at_slot,
+                    args.max_bytes,
+                    args.allowed_instructions,
+                    bump_seed,
+                );
+                session.serialize(&mut &mut session_account.data.borrow_mut()[..])?;
+
+                if args.debug {
+                    msg!(
+                        "registered session key {} until slot {}",
+                        args.session_key,
+                        args.expires_at_slot
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::RevokeSession(args) => {
+                if args.debug {
+                    msg!("RevokeSession");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let session_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority and session_account are writable
+                if !authority.is_writable || !session_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure session key is being revoked by the write authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure session_account is a session pda of the data_account
+                let session = Self::load_session(program_id, data_account, session_account)?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // transfer session_account lamports back to authority and reset session_account
+                let curr_lamports = authority.lamports();
+                **authority.lamports.borrow_mut() = curr_lamports
+                    .checked_add(session_account.lamports())
+                    .ok_or(DataAccountError::Overflow)?;
+                **session_account.lamports.borrow_mut() = 0;
+                session_account.data.borrow_mut().fill(0);
+
+                if args.debug {
+                    msg!("revoked session key {}", session.session_key());
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::ApproveDelegate(args) => {
+                if args.debug {
+                    msg!("ApproveDelegate");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let delegation_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority and delegation_account are writable
+                if !authority.is_writable || !delegation_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure program is being approved by the write authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure ranges are non-empty and within the maximum
+                if args.ranges.len() > MAX_DELEGATE_RANGES
+                    || args.ranges.iter().any(|range| range.start >= range.end)
+                {
+                    return Err(DataAccountError::InvalidDelegateRange.into());
+                }
+
+                // ensure the delegation pda is valid
+                let (pda, bump_seed) = Pubkey::find_program_address(
+                    &[
+                        DELEGATION_SEED,
+                        data_account.key.as_ref(),
+                        args.program.as_ref(),
+                    ],
+                    program_id,
+                );
+                if pda != *delegation_account.key {
+                    return Err(DataAccountError::InvalidDelegation.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // create the delegation pda unless the program is being approved again
+                if delegation_account.owner != program_id {
+                    let rent_exemption_amount = Rent::get()?.minimum_balance(DELEGATION_SIZE);
+                    let create_pda_ix = system_instruction::create_account(
+                        authority.key,
+                        delegation_account.key,
+                        rent_exemption_amount,
+                        DELEGATION_SIZE as u64,
+                        program_id,
+                    );
+                    invoke_signed(
+                        &create_pda_ix,
+                        &[
+                            authority.clone(),
+                            delegation_account.clone(),
+                            system_program.clone(),
+                        ],
+                        &[&[
+                            DELEGATION_SEED,
+                            data_account.key.as_ref(),
+                            args.program.as_ref(),
+                            &[bump_seed],
+                        ]],
+                    )?;
+
+                    if args.debug {
+                        msg!("delegation pda created");
+                    }
+                }
+
+                let delegation = Delegation::new(
+                    *data_account.key,
+                    args.program,
+                    *authority.key,
+                    &args.ranges,
+                    bump_seed,
+                );
+                delegation.serialize(&mut &mut delegation_account.data.borrow_mut()[..])?;
+
+                if args.debug {
+                    msg!(
+                        "approved program {} for {} ranges",
+                        args.program,
+                        args.ranges.len()
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::RevokeDelegate(args) => {
+                if args.debug {
+                    msg!("RevokeDelegate");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let delegation_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority and delegation_account are writable
+                if !authority.is_writable || !delegation_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure program is being revoked by the write authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure delegation_account is a delegation pda of the data_account
+                let delegation =
+                    Self::load_delegation(program_id, data_account, delegation_account)?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // transfer delegation_account lamports back to authority and reset it
+                let curr_lamports = authority.lamports();
+                **authority.lamports.borrow_mut() = curr_lamports
+                    .checked_add(delegation_account.lamports())
+                    .ok_or(DataAccountError::Overflow)?;
+                **delegation_account.lamports.borrow_mut() = 0;
+                delegation_account.data.borrow_mut().fill(0);
+
+                if args.debug {
+                    msg!("revoked program {}", delegation.program());
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::DelegatedUpdateDataAccount(args) => {
+                if args.debug {
+                    msg!("DelegatedUpdateDataAccount");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let delegate = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+                let delegation_account = next_account_info(accounts_iter)?;
+
+                // ensure delegate is signer
+                if !delegate.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure delegate, data_account, and metadata_account are writable
+                if !delegate.is_writable
+                    || !data_account.is_writable
+                    || !metadata_account.is_writable
+                {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure the program was approved by the current write authority
+                let delegation =
+                    Self::load_delegation(program_id, data_account, delegation_account)?;
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    delegation.authority(),
+                    accounts,
+                )
+                .map_err(|_| DataAccountError::InvalidDelegation)?;
+
+                // ensure delegate is the delegate signer pda of the approved program
+                let (delegate_signer, _) =
+                    Pubkey::find_program_address(&[DELEGATE_SIGNER_SEED], delegation.program());
+                if delegate_signer != *delegate.key {
+                    return Err(DataAccountError::InvalidAuthority.into());
+                }
+
+                // ensure a program limited to byte ranges writes within them without resizing
+                // or changing the data_type
+                if delegation.ranges().next().is_some() {
+                    let old_len = data_account.data_len() as u64;
+                    let end_len = args.offset.saturating_add(args.data.len() as u64);
+                    if !delegation.allows(args.offset, end_len)
+                        || args.data_type != *account_metadata.data_type()
+                        || end_len > old_len
+                        || (account_metadata.dynamic() && args.realloc_down && end_len < old_len)
+                    {
+                        return Err(DataAccountError::OutsideDelegateRange.into());
+                    }
+                }
+
+                if args.debug {
+                    msg!("delegated by {}", delegation.program());
+                }
+
+                Self::write_data(
+                    delegate,
+                    Self::refund_account(accounts, &account_metadata),
+                    data_account,
+                    metadata_account,
+                    system_program,
+                    account_metadata,
+                    UpdateDataAccountArgs {
+                        data_type: args.data_type,
+                        data: args.data,
+                        offset: args.offset,
+                        realloc_down: args.realloc_down,
+                        verify_flag: args.verify_flag,
+                        allow_sparse: args.allow_sparse,
+                        fill_byte: args.fill_byte,
+                        debug: args.debug,
+                    },
+                )
+            }
+            DataAccountInstruction::SetTokenGate(args) => {
+                if args.debug {
+                    msg!("SetTokenGate");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure token gate is being set by the authority manager
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::MANAGER,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure a token gate requires at least one token
+                let token_gate = if args.mint == Pubkey::default() {
+                    None
+                } else if args.min_amount == 0 {
+                    return Err(DataAccountError::InvalidTokenGate.into());
+                } else {
+                    Some((args.mint, args.min_amount))
+                };
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the token gate
+                account_metadata.set_token_gate(token_gate);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!(
+                        "token gate set to {} of mint {}",
+                        args.min_amount,
+                        args.mint
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::BindToNft(args) => {
+                if args.debug {
+                    msg!("BindToNft");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let mint_account = next_account_info(accounts_iter)?;
+                let token_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority and metadata_account are writable
+                if !authority.is_writable || !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is not bound to an nft already
+                if account_metadata.nft_mint().is_some() {
+                    return Err(DataAccountError::AlreadyNftBound.into());
+                }
+
+                // ensure data_account is being bound by the authority manager
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::MANAGER,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure mint_account is an nft mint that can never mint another token
+                if *mint_account.owner != spl_token::id() {
+                    return Err(DataAccountError::InvalidNftMint.into());
+                }
+                let mint = spl_token::state::Mint::unpack(&mint_account.try_borrow_data()?)
+                    .map_err(|_| DataAccountError::InvalidNftMint)?;
+                if mint.supply != 1 || mint.decimals != 0 || mint.mint_authority.is_some() {
+                    return Err(DataAccountError::InvalidNftMint.into());
+                }
+
+                // ensure authority holds the nft so that the data account is not handed away by accident
+                Self::check_token_account(authority.key, token_account, mint_account.key, 1)?;
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // move the usage of the data_account to the mint it is bound to
+                Self::move_usage(
+                    program_id,
+                    accounts,
+                    authority,
+                    data_account,
+                    account_metadata.authority(),
+                    mint_account.key,
+                )?;
+
+                // bind the data_account to the nft
+                account_metadata.bind_nft(*mint_account.key);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("bound to nft {}", mint_account.key);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::SetAppendOnly(args) => {
+                if args.debug {
+                    msg!("SetAppendOnly");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is made append-only by the finalize authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::FINALIZE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure data_account holds no data that was not appended as a record
+                if *account_metadata.data_type() != DataTypeOption::LOG
+                    && !data_account.data_is_empty()
+                {
+                    return Err(DataAccountError::LogNotEmpty.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // turn the data_account into a log
+                account_metadata.set_data_type(DataTypeOption::LOG);
+                account_metadata.set_public_log(args.public_appends);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("append-only with public appends {}", args.public_appends);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::AppendRecord(args) => {
+                if args.debug {
+                    msg!("AppendRecord");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let appender = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure appender is signer
+                if !appender.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure appender and data_account are writable
+                if !appender.is_writable || !data_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is a log
+                if *account_metadata.data_type() != DataTypeOption::LOG {
+                    return Err(DataAccountError::NotLog.into());
+                }
+
+                // ensure the treasury of a priced log is passed right after the system_program
+                let treasury = match account_metadata.append_price() {
+                    Some(_) => {
+                        let treasury = next_account_info(accounts_iter)?;
+                        if Some(treasury.key) != account_metadata.append_treasury() {
+                            return Err(DataAccountError::InvalidAppendTreasury.into());
+                        }
+                        Some(treasury)
+                    }
+                    None => None,
+                };
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                let record = LogRecord {
+                    appender: *appender.key,
+                    slot: Clock::get()?.slot,
+                    data: &args.data,
+                };
+                let header = record.header()?;
+
+                // ensure the record is appended by a writer unless anyone can append
+                if !account_metadata.public_log() {
+                    Self::check_writer(
+                        program_id,
+                        accounts,
+                        appender,
+                        data_account,
+                        &account_metadata,
+                        SessionInstructionOption::APPEND,
+                        record.size() as u64,
+                    )?;
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // pay the append price to the treasury
+                if let (Some((price, is_price_per_byte)), Some(treasury)) =
+                    (account_metadata.append_price(), treasury)
+                {
+                    let fee = if is_price_per_byte {
+                        price
+                            .checked_mul(record.data.len() as u64)
+                            .ok_or(DataAccountError::Overflow)?
+                    } else {
+                        price
+                    };
+                    if fee != 0 && appender.key != treasury.key {
+                        invoke(
+                            &system_instruction::transfer(appender.key, treasury.key, fee),
+                            &[appender.clone(), treasury.clone(), system_program.clone()],
+                        )?;
+
+                        if args.debug {
+                            msg!("paid {} to {}", fee, treasury.key);
+                        }
+                    }
+                }
+
+                // grow the data_account by the record and write it at the old end
+                let old_len = data_account.data_len();
+                let new_len = old_len
+                    .checked_add(record.size())
+                    .ok_or(DataAccountError::Overflow)?;
+                Self::resize_data_account(appender, None, data_account, system_program, new_len)?;
+
+                let mut data = data_account.data.borrow_mut();
+                data[old_len..old_len + LOG_RECORD_HEADER_SIZE].copy_from_slice(&header);
+                data[old_len + LOG_RECORD_HEADER_SIZE..new_len].copy_from_slice(record.data);
+
+                if args.debug {
+                    msg!("appended {} bytes at {}", record.data.len(), old_len);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::SetAppendPrice(args) => {
+                if args.debug {
+                    msg!("SetAppendPrice");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure metadata_account is writable
+                if !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is a log
+                if *account_metadata.data_type() != DataTypeOption::LOG {
+                    return Err(DataAccountError::NotLog.into());
+                }
+
+                // ensure append price is being set by the authority manager
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::MANAGER,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // resolve the treasury now so that it does not follow later authority changes
+                let treasury = if args.treasury == Pubkey::default() {
+                    *account_metadata.authority()
+                } else {
+                    args.treasury
+                };
+
+                // ensure a priced log is paid neither to the mint of its nft nor to nobody
+                if args.price != 0
+                    && (treasury == Pubkey::default()
+                        || account_metadata.nft_mint() == Some(&treasury))
+                {
+                    return Err(DataAccountError::InvalidAppendTreasury.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the append price
+                account_metadata.set_append_price(
+                    Some((args.price, args.per_byte, treasury)).filter(|(price, ..)| *price != 0),
+                );
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!(
+                        "append price set to {} per {} paid to {}",
+                        args.price,
+                        if args.per_byte { "byte" } else { "record" },
+                        treasury
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::InitializeRingBuffer(args) => {
+                if args.debug {
+                    msg!("InitializeRingBuffer");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure authority, data_account, and metadata_account are writable
+                if !authority.is_writable
+                    || !data_account.is_writable
+                    || !metadata_account.is_writable
+                {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure data_account is not an append-only log
+                if *account_metadata.data_type() == DataTypeOption::LOG {
+                    return Err(DataAccountError::AppendOnly.into());
+                }
+
+                // ensure data_account is turned into a ring buffer by the write authority
+                Self::check_role(
+                    &account_metadata,
+                    RoleOption::WRITE,
+                    authority.key,
+                    accounts,
+                )?;
+
+                // ensure the ring buffer holds at least one record
+                if args.record_size == 0 || args.capacity == 0 {
+                    return Err(DataAccountError::InvalidRingBuffer.into());
+                }
+                let header = RingHeader::new(args.record_size);
+                let new_len = header.offset(args.capacity)?;
+
+                // ensure no frozen data is cleared
+                let old_len = data_account.data_len();
+                if account_metadata.is_frozen(0, old_len.max(new_len) as u64) {
+                    return Err(DataAccountError::RangeFrozen.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // update the metadata_account
+                account_metadata.set_data_type(DataTypeOption::RING);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                // resize the data_account to fit every record and clear it
+                if old_len != new_len {
+                    Self::resize_data_account(
+                        authority,
+                        Self::refund_account(accounts, &account_metadata),
+                        data_account,
+                        system_program,
+                        new_len,
+                    )?;
+                }
+                data_account.data.borrow_mut().fill(0);
+                header.serialize(&mut &mut data_account.data.borrow_mut()[..RING_HEADER_SIZE])?;
+
+                if args.debug {
+                    msg!(
+                        "ring buffer of {} records of {} bytes",
+                        args.capacity,
+                        args.record_size
+                    );
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::PushRecord(args) => {
+                if args.debug {
+                    msg!("PushRecord");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let authority = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+
+                // ensure authority is signer
+                if !authority.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure data_account is writable
+                if !data_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                let account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure data_account is a ring buffer
+                if *account_metadata.data_type() != DataTypeOption::RING {
+                    return Err(DataAccountError::NotRingBuffer.into());
+                }
+
+                // ensure data_account is not finalized
+                if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+                    return Err(DataAccountError::AlreadyFinalized.into());
+                }
+
+                // ensure data_account is not frozen by the arbiter
+                if account_metadata.arbiter_frozen() {
+                    return Err(DataAccountError::AccountFrozen.into());
+                }
+
+                // ensure the record is pushed by the write authority or its session key
+                Self::check_writer(
+                    program_id,
+                    accounts,
+                    authority,
+                    data_account,
+                    &account_metadata,
+                    SessionInstructionOption::APPEND,
+                    args.data.len() as u64,
+                )?;
+
+                // ensure the header fits the data_account
+                let (mut header, capacity) = RingHeader::unpack(&data_account.try_borrow_data()?)?;
+
+                // ensure the record fills exactly one slot
+                if args.data.len() as u64 != header.record_size {
+                    return Err(DataAccountError::InvalidRecordSize.into());
+                }
+
+                // ensure neither the header nor the slot at the head is frozen
+                let offset = header.offset(header.head)?;
+                if account_metadata.is_frozen(0, RING_HEADER_SIZE as u64)
+                    || account_metadata.is_frozen(offset as u64, (offset + args.data.len()) as u64)
+                {
+                    return Err(DataAccountError::RangeFrozen.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // overwrite the slot at the head and advance it
+                let mut data = data_account.data.borrow_mut();
+                data[offset..offset + args.data.len()].copy_from_slice(&args.data);
+                header.push(capacity);
+                header.serialize(&mut &mut data[..RING_HEADER_SIZE])?;
+
+                if args.debug {
+                    msg!("pushed record {} of {}", header.count, capacity);
+                }
+
+                Ok(())
+            }
+            DataAccountInstruction::MigrateMetadata(args) => {
+                if args.debug {
+                    msg!("MigrateMetadata");
+                }
+
+                let accounts_iter = &mut accounts.iter();
+                let payer = next_account_info(accounts_iter)?;
+                let data_account = next_account_info(accounts_iter)?;
+                let metadata_account = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+
+                // ensure payer is signer
+                if !payer.is_signer {
+                    return Err(DataAccountError::NotSigner.into());
+                }
+
+                // ensure payer and metadata_account are writable
+                if !payer.is_writable || !metadata_account.is_writable {
+                    return Err(DataAccountError::NotWriteable.into());
+                }
+
+                // ensure system_program is valid
+                Self::check_system_program(system_program)?;
+
+                let mut account_metadata =
+                    Self::load_metadata(program_id, data_account, metadata_account)?;
+
+                // ensure metadata_account is in the legacy layout
+                if metadata_account.data_len() >= METADATA_SIZE {
+                    return Err(DataAccountError::AlreadyMigrated.into());
+                }
+
+                if args.debug {
+                    msg!("account checks passed");
+                }
+
+                // grow metadata_account to the current layout, funded by the payer
+                Self::resize_data_account(
+                    payer,
+                    None,
+                    metadata_account,
+                    system_program,
+                    METADATA_SIZE,
+                )?;
+
+                account_metadata.set_version(DATA_VERSION);
+                Self::store_metadata(&account_metadata, metadata_account)?;
+
+                if args.debug {
+                    msg!("migrated metadata to version {}", DATA_VERSION);
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Ensures the config pda is passed as the last account and that it allows the instruction,
+    /// collecting the protocol fee when a data account is initialized, and returns the config
+    /// if it is initialized
+    fn check_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction: &DataAccountInstruction,
+    ) -> Result<Option<DataProgramConfig>, ProgramError> {
+        match instruction {
+            DataAccountInstruction::InitializeConfig(_)
+            | DataAccountInstruction::UpdateConfig(_)
+            | DataAccountInstruction::WithdrawProtocolFees(_)
+            | DataAccountInstruction::SetUsageQuota(_) => return Ok(None),
+            _ => {}
+        }
+
+        // the config pda is found by its address, and a missing or uninitialized config imposes
+        // no restrictions
+        let (pda, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+        let config_account = match Self::optional_account(accounts, &pda) {
+            Some(config_account)
+                if config_account.owner == program_id && !config_account.data_is_empty() =>
+            {
+                config_account
+            }
+            _ => return Ok(None),
+        };
+        let config = DataProgramConfig::try_from_slice(&config_account.try_borrow_data()?)?;
+
+        // ensure the data program is not paused
+        if config.paused() {
+            return Err(DataAccountError::ProgramPaused.into());
+        }
+
+        // ensure the data account does not grow past the maximum account size
+        let account_size = match instruction {
+            DataAccountInstruction::InitializeDataAccount(args) if args.is_created => accounts
+                .get(1)
+                .map(|data_account| data_account.data_len() as u64),
+            DataAccountInstruction::InitializeDataAccount(args) => Some(args.space),
+            DataAccountInstruction::UpdateDataAccount(args) => {
+                Some(args.offset.saturating_add(args.data.len() as u64))
+            }
+            DataAccountInstruction::RelayedUpdateDataAccount(args) => {
+                Some(args.offset.saturating_add(args.data.len() as u64))
+            }
+            DataAccountInstruction::DelegatedUpdateDataAccount(args) => {
+                Some(args.offset.saturating_add(args.data.len() as u64))
+            }
+            DataAccountInstruction::ReserveSpace(args) => Some(args.space),
+            DataAccountInstruction::ResizeDataAccount(args) => Some(args.len),
+            DataAccountInstruction::AppendRecord(args) => accounts.get(1).map(|data_account| {
+                (data_account.data_len() as u64)
+                    .saturating_add((LOG_RECORD_HEADER_SIZE + args.data.len()) as u64)
+            }),
+            DataAccountInstruction::InitializeRingBuffer(args) => Some(
+                args.record_size
+                    .saturating_mul(args.capacity)
+                    .saturating_add(RING_HEADER_SIZE as u64),
+            ),
+            _ => None,
+        };
+        if let Some(account_size) = account_size {
+            if config.max_account_size() != 0 && account_size > config.max_account_size() {
+                return Err(DataAccountError::AccountSizeLimitExceeded.into());
+            }
+        }
+
+        // ensure the data written does not exceed the maximum write size
+        let write_size = match instruction {
+            DataAccountInstruction::UpdateDataAccount(args) => Some(args.data.len() as u64),
+            DataAccountInstruction::RelayedUpdateDataAccount(args) => Some(args.data.len() as u64),
+            DataAccountInstruction::DelegatedUpdateDataAccount(args) => {
+                Some(args.data.len() as u64)
+            }
+            DataAccountInstruction::AppendRecord(args) => {
+                Some((LOG_RECORD_HEADER_SIZE + args.data.len()) as u64)
+            }
+            DataAccountInstruction::PushRecord(args) => Some(args.data.len() as u64),
+            _ => None,
+        };
+        if let Some(write_size) = write_size {
+            if config.max_write_size() != 0 && write_size > config.max_write_size() {
+                return Err(DataAccountError::WriteSizeLimitExceeded.into());
+            }
+        }
+
+        // collect the protocol fee and the storage fee for the bytes the data account grows by
+        // from the payer, which is always the first account, unless the authority of the data
+        // account is exempt
+        let payer = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let authority = match instruction {
+            DataAccountInstruction::InitializeDataAccount(args) => Some(args.authority),
+            _ => accounts
+                .get(2)
+                .and_then(|metadata_account| {
+                    DataAccountMetadata::unpack(&metadata_account.try_borrow_data().ok()?).ok()
+                })
+                .map(|account_metadata| *account_metadata.authority()),
+        };
+        let data_len = accounts
+            .get(1)
+            .map_or(0, |data_account| data_account.data_len() as u64);
+        let (protocol_fee, growth) = match instruction {
+            DataAccountInstruction::InitializeDataAccount(_) => {
+                (config.protocol_fee(), account_size.unwrap_or(0))
+            }
+            DataAccountInstruction::ReserveSpace(args) => (
+                0,
+                args.space
+                    .min(data_len.saturating_add(MAX_PERMITTED_DATA_INCREASE as u64))
+                    .saturating_sub(data_len),
+            ),
+            _ => (
+                0,
+                account_size.map_or(0, |account_size| account_size.saturating_sub(data_len)),
+            ),
+        };
+        let storage_fee =
+            if growth == 0 || authority.is_some_and(|authority| config.is_fee_exempt(&authority)) {
+                0
+            } else {
+                growth
+                    .checked_mul(config.storage_fee_per_byte())
+                    .ok_or(DataAccountError::Overflow)?
+            };
+        let fee = protocol_fee
+            .checked_add(storage_fee)
+            .ok_or(DataAccountError::Overflow)?;
+
+        if fee != 0 {
+            let system_program = Self::optional_account(accounts, &system_program::id())
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            let transfer_ix = system_instruction::transfer(payer.key, config_account.key, fee);
+            invoke(
+                &transfer_ix,
+                &[
+                    payer.clone(),
+                    config_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+
+            // always logged so that fees can be reconciled off-chain
+            msg!(
+                "fee collected: payer={} authority={} protocol_fee={} storage_fee={} bytes={}",
+                payer.key,
+                authority.unwrap_or_default(),
+                protocol_fee,
+                storage_fee,
+                growth
+            );
+        }
+
+        Ok(Some(config))
+    }
+
+    /// Ensures the config_account is the initialized config pda and returns the config
+    fn load_config(
+        program_id: &Pubkey,
+        config_account: &AccountInfo,
+    ) -> Result<DataProgramConfig, ProgramError> {
+        // ensure config_account is owned by the data program
+        if config_account.owner != program_id {
+            return Err(DataAccountError::InvalidOwner.into());
+        }
+
+        // ensure length is not 0
+        if config_account.data_is_empty() {
+            return Err(DataAccountError::NoAccountLength.into());
+        }
+
+        let config = DataProgramConfig::try_from_slice(&config_account.try_borrow_data()?)?;
+
+        // ensure config_account is the config pda
+        let pda =
+            Pubkey::create_program_address(&[CONFIG_SEED, &[config.bump_seed()]], program_id)?;
+        if pda != *config_account.key {
+            return Err(DataAccountError::InvalidConfig.into());
+        }
+
+        Ok(config)
+    }
+
+    /// Updates the usage pda of the authority, if it is passed, with the change in the bytes and
+    /// data accounts the data_account allocates and, if `enforce_quota` is set, ensures it stays
+    /// within its quota
+    fn track_usage<'a>(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo<'a>],
+        data_account: &AccountInfo<'a>,
+        authority: &Pubkey,
+        old_bytes: u64,
+        old_accounts: u64,
+        enforce_quota: bool,
+    ) -> ProgramResult {
+        // a closed data account no longer counts towards the usage
+        let (new_bytes, new_accounts) = if data_account.lamports() == 0 {
+            (0, 0)
+        } else {
+            (data_account.data_len() as u64, 1)
+        };
+        if new_bytes == old_bytes && new_accounts == old_accounts {
+            return Ok(());
+        }
+
+        let (usage_account, bump_seed) = match Self::usage_account(program_id, accounts, authority)?
+        {
+            Some(usage_account) => usage_account,
+            None => return Ok(()),
+        };
+
+        let mut usage = if usage_account.owner == program_id {
+            AuthorityUsage::try_from_slice(&usage_account.try_borrow_data()?)?
+        } else {
+            // there is nothing to release if the authority was never tracked
+            if new_bytes <= old_bytes && new_accounts <= old_accounts {
+                return Ok(());
+            }
+
+            let payer = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+            Self::create_usage_account(
+                program_id,
+                accounts,
+                payer,
+                usage_account,
+                authority,
+                bump_seed,
+            )?;
+            AuthorityUsage::new(*authority, bump_seed)
+        };
+
+        usage.set_bytes(
+            usage
+                .bytes()
+                .checked_add(new_bytes)
+                .ok_or(DataAccountError::Overflow)?
+                .saturating_sub(old_bytes),
+        );
+        usage.set_accounts(
+            usage
+                .accounts()
+                .checked_add(new_accounts)
+                .ok_or(DataAccountError::Overflow)?
+                .saturating_sub(old_accounts),
+        );
+
+        // ensure the authority does not allocate more than its quota
+        if enforce_quota
+            && ((new_bytes > old_bytes
+                && usage.max_bytes() != 0
+                && usage.bytes() > usage.max_bytes())
+                || (new_accounts > old_accounts
+                    && usage.max_accounts() != 0
+                    && usage.accounts() > usage.max_accounts()))
+        {
+            return Err(DataAccountError::QuotaExceeded.into());
+        }
+
+        usage.serialize(&mut &mut usage_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    /// Returns the usage pda of the authority and its bump seed if it is passed, ensuring it is
+    /// writable
+    fn usage_account<'a, 'b>(
+        program_id: &Pubkey,
+        accounts: &'b [AccountInfo<'a>],
+        authority: &Pubkey,
+    ) -> Result<Option<(&'b AccountInfo<'a>, u8)>, ProgramError> {
+        let (pda, bump_seed) =
+            Pubkey::find_program_address(&[USAGE_SEED, authority.as_ref()], program_id);
+        match Self::optional_account(accounts, &pda) {
+            Some(usage_account) if !usage_account.is_writable => {
+                Err(DataAccountError::NotWriteable.into())
+            }
+            Some(usage_account) => Ok(Some((usage_account, bump_seed))),
+            None => Ok(None),
+        }
+    }
+
+    /// Creates the usage pda of the authority funded by the payer, finding the system program
+    /// among the accounts by its address
+    fn create_usage_account<'a>(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo<'a>],
+        payer: &AccountInfo<'a>,
+        usage_account: &AccountInfo<'a>,
+        authority: &Pubkey,
+        bump_seed: u8,
+    ) -> ProgramResult {
+        let system_program = Self::optional_account(accounts, &system_program::id())
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let rent_exemption_amount = Rent::get()?.minimum_balance(USAGE_SIZE);
+        let create_pda_ix = system_instruction::create_account(
+            payer.key,
+            usage_account.key,
+            rent_exemption_amount,
+            USAGE_SIZE as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_pda_ix,
+            &[payer.clone(), usage_account.clone(), system_program.clone()],
+            &[&[USAGE_SEED, authority.as_ref(), &[bump_seed]]],
+        )
+    }
+
+    /// Moves the bytes and data account allocated by the data_account from the usage pda of the
+    /// old authority to the usage pda of the new authority, creating the latter funded by the
+    /// payer, where either usage pda is skipped if it is not passed
+    ///
+    /// The new authority is charged even past its quota, which only blocks further growth
+    fn move_usage<'a>(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo<'a>],
+        payer: &AccountInfo<'a>,
+        data_account: &AccountInfo<'a>,
+        old_authority: &Pubkey,
+        new_authority: &Pubkey,
+    ) -> ProgramResult {
+        if old_authority == new_authority {
+            return Ok(());
+        }
+
+        let bytes = data_account.data_len() as u64;
+
+        // release the data_account from the old authority if it was ever tracked
+        if let Some((old_usage_account, _)) =
+            Self::usage_account(program_id, accounts, old_authority)?
+        {
+            if old_usage_account.owner == program_id {
+                let mut usage =
+                    AuthorityUsage::try_from_slice(&old_usage_account.try_borrow_data()?)?;
+                usage.set_bytes(usage.bytes().saturating_sub(bytes));
+                usage.set_accounts(usage.accounts().saturating_sub(1));
+                usage.serialize(&mut &mut old_usage_account.data.borrow_mut()[..])?;
+            }
+        }
+
+        // charge the data_account to the new authority
+        let (new_usage_account, bump_seed) =
+            match Self::usage_account(program_id, accounts, new_authority)? {
+                Some(new_usage_account) => new_usage_account,
+                None => return Ok(()),
+            };
+        let mut usage = if new_usage_account.owner == program_id {
+            AuthorityUsage::try_from_slice(&new_usage_account.try_borrow_data()?)?
+        } else {
+            Self::create_usage_account(
+                program_id,
+                accounts,
+                payer,
+                new_usage_account,
+                new_authority,
+                bump_seed,
+            )?;
+            AuthorityUsage::new(*new_authority, bump_seed)
+        };
+        usage.set_bytes(
+            usage
+                .bytes()
+                .checked_add(bytes)
+                .ok_or(DataAccountError::Overflow)?,
+        );
+        usage.set_accounts(
+            usage
+                .accounts()
+                .checked_add(1)
+                .ok_or(DataAccountError::Overflow)?,
+        );
+        usage.serialize(&mut &mut new_usage_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    /// Reallocs the data_account to new_len, zero-filling and topping up the rent exemption from
+    /// the payer when growing and refunding the excess lamports to the refund account when
+    /// shrinking, where the excess stays in the data_account if there is no refund account
+    fn resize_data_account<'a>(
+        payer: &AccountInfo<'a>,
+        refund_account: Option<&AccountInfo<'a>>,
+        data_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        new_len: usize,
+    ) -> ProgramResult {
+        let old_len = data_account.data_len();
+
+        // ensure the growth is within the runtime's per-instruction realloc limit
+        if new_len.saturating_sub(old_len) > MAX_PERMITTED_DATA_INCREASE {
+            return Err(DataAccountError::ReallocLimitExceeded.into());
+        }
+
+        let new_minimum_balance = Rent::get()?.minimum_balance(new_len);
+
+        if old_len < new_len {
+            let lamports_diff = new_minimum_balance.saturating_sub(data_account.lamports());
+            let transfer_ix =
+                system_instruction::transfer(payer.key, data_account.key, lamports_diff);
+            invoke(
+                &transfer_ix,
+                &[payer.clone(), data_account.clone(), system_program.clone()],
+            )?;
+        } else if let Some(refund_account) = refund_account {
+            let lamports_diff = data_account.lamports().saturating_sub(new_minimum_balance);
+            let refund_lamports = refund_account.lamports();
+            **refund_account.lamports.borrow_mut() = refund_lamports
+                .checked_add(lamports_diff)
+                .ok_or(DataAccountError::Overflow)?;
+            let data_lamports = data_account.lamports();
+            **data_account.lamports.borrow_mut() = data_lamports - lamports_diff;
+        }
+
+        data_account.realloc(new_len, true)
+    }
+
+    /// Returns the authority of the data account to refund the excess lamports of a shrinking
+    /// data account to if it is passed and writable, so that the lamports never go to a role
+    /// holder or a session key, or none if the data account is bound to an nft
+    fn refund_account<'a, 'b>(
+        accounts: &'b [AccountInfo<'a>],
+        account_metadata: &DataAccountMetadata,
+    ) -> Option<&'b AccountInfo<'a>> {
+        if account_metadata.nft_mint().is_some() {
+            return None;
+        }
+        Self::optional_account(accounts, account_metadata.authority())
+            .filter(|authority| authority.is_writable)
+    }
+
+    /// Ensures the system_program is the Solana System Program
+    fn check_system_program(system_program: &AccountInfo) -> ProgramResult {
+        if !system_program::check_id(system_program.key) {
+            return Err(DataAccountError::InvalidSysProgram.into());
+        }
+        Ok(())
+    }
+
+    /// Ensures the metadata_account is the initialized metadata PDA of the data_account
+    /// and returns its metadata, which is finalized if its scheduled finalization has passed
+    fn load_metadata(
+        program_id: &Pubkey,
+        data_account: &AccountInfo,
+        metadata_account: &AccountInfo,
+    ) -> Result<DataAccountMetadata, ProgramError> {
+        // ensure data_account and metadata_account are owned by the data program
+        if data_account.owner != program_id || metadata_account.owner != program_id {
+            return Err(DataAccountError::InvalidOwner.into());
+        }
+
+        // ensure length is not 0
+        if metadata_account.data_is_empty() {
+            return Err(DataAccountError::NoAccountLength.into());
+        }
+
+        let mut account_metadata =
+            DataAccountMetadata::unpack(&metadata_account.try_borrow_data()?)?;
+
+        // ensure data_account is initialized
+        if *account_metadata.data_status() == DataStatusOption::UNINITIALIZED {
+            return Err(DataAccountError::NotInitialized.into());
+        }
+
+        // ensure the metadata_account corresponds to the data_account
+        let pda = Pubkey::create_program_address(
+            &[
+                PDA_SEED,
+                data_account.key.as_ref(),
+                &[account_metadata.bump_seed()],
+            ],
+            program_id,
+        )?;
+        if pda != *metadata_account.key {
+            return Err(DataAccountError::InvalidPDA.into());
+        }
+
+        // treat data_account as finalized once either scheduled finalization has passed
+        if account_metadata.finalize_scheduled() {
+            let clock = Clock::get()?;
+            if matches!(account_metadata.finalize_at(), Some(at) if clock.unix_timestamp >= at)
+                || matches!(account_metadata.finalize_at_slot(), Some(slot) if clock.slot >= slot)
+            {
+                account_metadata.set_data_status(DataStatusOption::FINALIZED);
+            }
+        }
+
+        Ok(account_metadata)
+    }
+
+    /// Ensures the data_account is a writable, initialized and unfinalized data account
+    /// that the authority holds the write role of and returns its metadata
+    fn load_writable_data_account(
+        program_id: &Pubkey,
+        authority: &AccountInfo,
+        accounts: &[AccountInfo],
+        data_account: &AccountInfo,
+        metadata_account: &AccountInfo,
+    ) -> Result<DataAccountMetadata, ProgramError> {
+        // ensure data_account and metadata_account are writable
+        if !data_account.is_writable || !metadata_account.is_writable {
+            return Err(DataAccountError::NotWriteable.into());
+        }
+
+        let account_metadata = Self::load_metadata(program_id, data_account, metadata_account)?;
+
+        // ensure data_account is not finalized
+        if *account_metadata.data_status() == DataStatusOption::FINALIZED {
+            return Err(DataAccountError::AlreadyFinalized.into());
+        }
+
+        // ensure data_account is not frozen by the arbiter
+        if account_metadata.arbiter_frozen() {
+            return Err(DataAccountError::AccountFrozen.into());
+        }
+
+        // ensure data_account is being written to by the write authority
+        Self::check_role(
+            &account_metadata,
+            RoleOption::WRITE,
+            authority.key,
+            accounts,
+        )?;
+
+        Ok(account_metadata)
+    }
+
+    /// Ensures the directory_account is a writable directory of the authority
+    /// and returns its metadata and entries
+    fn load_directory(
+        program_id: &Pubkey,
+        authority: &AccountInfo,
+        accounts: &[AccountInfo],
+        directory_account: &AccountInfo,
+        metadata_account: &AccountInfo,
+    ) -> Result<(DataAccountMetadata, Directory), ProgramError> {
+        let account_metadata = Self::load_writable_data_account(
+            program_id,
+            authority,
+            accounts,
+            directory_account,
+            metadata_account,
+        )?;
+
+        // ensure directory_account holds a directory
+        if *account_metadata.data_type() != DataTypeOption::DIRECTORY {
+            return Err(DataAccountError::NotDirectory.into());
+        }
+        let directory = Directory::unpack(&directory_account.try_borrow_data()?)
+            .map_err(|_| DataAccountError::NotDirectory)?;
+
+        Ok((account_metadata, directory))
+    }
+
+    /// Ensures the manifest_account is a writable manifest of the authority
+    /// and returns its metadata and shards
+    fn load_manifest(
+        program_id: &Pubkey,
+        authority: &AccountInfo,
+        accounts: &[AccountInfo],
+        manifest_account: &AccountInfo,
+        metadata_account: &AccountInfo,
+    ) -> Result<(DataAccountMetadata, Manifest), ProgramError> {
+        let account_metadata = Self::load_writable_data_account(
+            program_id,
+            authority,
+            accounts,
+            manifest_account,
+            metadata_account,
+        )?;
+
+        // ensure manifest_account holds a manifest
+        if *account_metadata.data_type() != DataTypeOption::MANIFEST {
+            return Err(DataAccountError::NotManifest.into());
+        }
+        let manifest = Manifest::unpack(&manifest_account.try_borrow_data()?)
+            .map_err(|_| DataAccountError::NotManifest)?;
+
+        Ok((account_metadata, manifest))
+    }
+
+    /// Writes the metadata to the metadata_account, which must have been migrated to the
+    /// current layout
+    fn store_metadata(
+        account_metadata: &DataAccountMetadata,
+        metadata_account: &AccountInfo,
+    ) -> ProgramResult {
+        // ensure metadata_account is not in the legacy layout
+        if metadata_account.data_len() < METADATA_SIZE {
+            return Err(DataAccountError::MetadataNotMigrated.into());
+        }
+
+        account_metadata.serialize(&mut &mut metadata_account.data.borrow_mut()[..])?;
+        Ok(())
+    }
+
+    /// Writes the serialized value to the data_account, reallocing it to fit if dynamic and
+    /// refunding the excess lamports of a shrinking data_account to its authority
+    fn store_data<'a, T: BorshSerialize>(
+        value: &T,
+        account_metadata: &DataAccountMetadata,
+        accounts: &[AccountInfo<'a>],
+        authority: &AccountInfo<'a>,
+        data_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let serialized = value.try_to_vec()?;
+
+        // ensure no frozen data is rewritten
+        if account_metadata.frozen_ranges().next().is_some() {
+            return Err(DataAccountError::RangeFrozen.into());
+        }
+
+        // ensure static data_account has sufficient space
+        if account_metadata.dynamic() {
+            if data_account.data_len() != serialized.len() {
+                Self::resize_data_account(
+                    authority,
+                    Self::refund_account(accounts, account_metadata),
+                    data_account,
+                    system_program,
+                    serialized.len(),
+                )?;
+            }
+        } else if data_account.data_len() < serialized.len() {
+            return Err(DataAccountError::InsufficientSpace.into());
+        }
+
+        let mut data = data_account.data.borrow_mut();
+        data[..serialized.len()].copy_from_slice(&serialized);
+        data[serialized.len()..].fill(0);
+
+        Ok(())
+    }
+
+    /// Ensures the instruction right before the current one is an ed25519 program instruction
+    /// verifying a single signature of the message and returns the key that signed it
+    fn load_relay_signer(
+        instructions_sysvar: &AccountInfo,
+        message: &[u8],
+    ) -> Result<Pubkey, ProgramError> {
+        let current_index = load_current_index_checked(instructions_sysvar)?;
+        let index = current_index
+            .checked_sub(1)
+            .ok_or(DataAccountError::InvalidRelaySignature)?;
+        let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+
+        // ensure instruction is verified by the ed25519 program
+        if instruction.program_id != ed25519_program::id() {
+            return Err(DataAccountError::InvalidRelaySignature.into());
+        }
+
+        Self::parse_relay_signer(&instruction.data, message)
+    }
+
+    /// Parses the data of an ed25519 program instruction, ensuring it verifies a single
+    /// signature of the message, and returns the key that signed it
+    fn parse_relay_signer(data: &[u8], message: &[u8]) -> Result<Pubkey, ProgramError> {
+        // ensure a single signature is verified whose key and message are within the instruction
+        if data.len() < 16 || data[0] != 1 {
+            return Err(DataAccountError::InvalidRelaySignature.into());
+        }
+        let offset = |i: usize| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]);
+        if offset(1) != u16::MAX || offset(3) != u16::MAX || offset(6) != u16::MAX {
+            return Err(DataAccountError::InvalidRelaySignature.into());
+        }
+        let public_key_offset = offset(2) as usize;
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(DataAccountError::InvalidRelaySignature)?;
+
+        // ensure the verified message is the expected one
+        let message_offset = offset(4) as usize;
+        if data.get(message_offset..message_offset + offset(5) as usize) != Some(message) {
+            return Err(DataAccountError::InvalidRelaySignature.into());
+        }
+
+        Pubkey::try_from(public_key).map_err(|_| DataAccountError::InvalidRelaySignature.into())
+    }
+
+    /// Ensures the session_account is an initialized session pda of the data_account
+    /// and returns the session key
+    fn load_session(
+        program_id: &Pubkey,
+        data_account: &AccountInfo,
+        session_account: &AccountInfo,
+    ) -> Result<SessionKey, ProgramError> {
+        // ensure session_account is owned by the data program and initialized
+        if session_account.owner != program_id || session_account.data_is_empty() {
+            return Err(DataAccountError::InvalidSession.into());
+        }
+
+        let session = SessionKey::try_from_slice(&session_account.try_borrow_data()?)?;
+
+        // ensure session_account is the session pda of the data_account
+        let pda = Pubkey::create_program_address(
+            &[
+                SESSION_SEED,
+                data_account.key.as_ref(),
+                session.session_key().as_ref(),
+                &[session.bump_seed()],
+            ],
+            program_id,
+        )?;
+        if pda != *session_account.key || session.data_account() != data_account.key {
+            return Err(DataAccountError::InvalidSession.into());
+        }
+
+        Ok(session)
+    }
+
+    /// Ensures the delegation_account is an initialized delegation pda of the data_account
+    /// and returns the delegation
+    fn load_delegation(
+        program_id: &Pubkey,
+        data_account: &AccountInfo,
+        delegation_account: &AccountInfo,
+    ) -> Result<Delegation, ProgramError> {
+        // ensure delegation_account is owned by the data program and initialized
+        if delegation_account.owner != program_id || delegation_account.data_is_empty() {
+            return Err(DataAccountError::InvalidDelegation.into());
+        }
+
+        let delegation = Delegation::try_from_slice(&delegation_account.try_borrow_data()?)?;
+
+        // ensure delegation_account is the delegation pda of the data_account
+        let pda = Pubkey::create_program_address(
+            &[
+                DELEGATION_SEED,
+                data_account.key.as_ref(),
+                delegation.program().as_ref(),
+                &[delegation.bump_seed()],
+            ],
+            program_id,
+        )?;
+        if pda != *delegation_account.key || delegation.data_account() != data_account.key {
+            return Err(DataAccountError::InvalidDelegation.into());
+        }
+
+        Ok(delegation)
+    }
+
+    /// Returns the optional account with the key, which is found by its address so that the
+    /// optional accounts can follow the accounts the instruction requires in any order
+    fn optional_account<'a, 'b>(
+        accounts: &'b [AccountInfo<'a>],
+        key: &Pubkey,
+    ) -> Option<&'b AccountInfo<'a>> {
+        accounts.iter().find(|account| account.key == key)
+    }
+
+    /// Returns the token account of the mint owned by the owner, if it is passed
+    fn find_token_account<'a, 'b>(
+        accounts: &'b [AccountInfo<'a>],
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Option<&'b AccountInfo<'a>> {
+        accounts.iter().find(|account| {
+            *account.owner == spl_token::id()
+                && account
+                    .try_borrow_data()
+                    .ok()
+                    .and_then(|data| spl_token::state::Account::unpack(&data).ok())
+                    .is_some_and(|token| token.owner == *owner && token.mint == *mint)
+        })
+    }
+
+    /// Ensures the authority holds the write role of the data_account or is a live session key
+    /// of the write authority that is allowed to sign the instruction, in which case its session
+    /// pda is passed and `bytes` are charged to its budget
+    ///
+    /// The write authority of an nft-bound data_account proves it holds the nft by passing its
+    /// token account, and so does a holder of the token gate unless the write role was renounced
+    ///
+    /// Returns whether the authority is only allowed to write by holding the token gate
+    fn check_writer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        authority: &AccountInfo,
+        data_account: &AccountInfo,
+        account_metadata: &DataAccountMetadata,
+        instruction: SessionInstructionOption,
+        bytes: u64,
+    ) -> Result<bool, ProgramError> {
+        if Self::check_role(account_metadata, RoleOption::WRITE, authority.key, accounts).is_ok() {
+            return Ok(false);
+        }
+
+        let (session_pda, _) = Pubkey::find_program_address(
+            &[
+                SESSION_SEED,
+                data_account.key.as_ref(),
+                authority.key.as_ref(),
+            ],
+            program_id,
+        );
+        let session_account = match Self::optional_account(accounts, &session_pda) {
+            Some(session_account) => session_account,
+            None => {
+                // a holder of the token gate can write unless the write role was renounced
+                let (mint, min_amount) = match account_metadata.token_gate() {
+                    Some(token_gate)
+                        if !account_metadata.renounced(RoleOption::WRITE)
+                            && matches!(
+                                instruction,
+                                SessionInstructionOption::UPDATE | SessionInstructionOption::APPEND
+                            ) =>
+                    {
+                        token_gate
+                    }
+                    _ => return Err(DataAccountError::InvalidAuthority.into()),
+                };
+                let token_account = Self::find_token_account(accounts, authority.key, mint)
+                    .ok_or(DataAccountError::InvalidAuthority)?;
+                Self::check_token_account(authority.key, token_account, mint, min_amount)?;
+                return Ok(true);
+            }
+        };
+
+        let mut session = Self::load_session(program_id, data_account, session_account)?;
+
+        // ensure session key was registered by the current write authority
+        if session.session_key() != authority.key
+            || Self::check_role(
+                account_metadata,
+                RoleOption::WRITE,
+                session.authority(),
+                accounts,
+            )
+            .is_err()
+        {
+            return Err(DataAccountError::InvalidSession.into());
+        }
+
+        // ensure session key is live and allowed to sign the instruction
+        if Clock::get()?.slot > session.expires_at_slot() {
+            return Err(DataAccountError::SessionExpired.into());
+        }
+        if !session.allows(instruction) {
+            return Err(DataAccountError::SessionNotAllowed.into());
+        }
+
+        // ensure session key stays within its byte budget
+        let bytes_written = session
+            .bytes_written()
+            .checked_add(bytes)
+            .ok_or(DataAccountError::Overflow)?;
+        if session.max_bytes() != 0 && bytes_written > session.max_bytes() {
+            return Err(DataAccountError::SessionBudgetExceeded.into());
+        }
+
+        // ensure session_account is writable to record the bytes written
+        if !session_account.is_writable {
+            return Err(DataAccountError::NotWriteable.into());
+        }
+
+        session.set_bytes_written(bytes_written);
+        session.serialize(&mut &mut session_account.data.borrow_mut()[..])?;
+
+        Ok(false)
+    }
+
+    /// Ensures the signer holds the role, where the holder of the nft of an nft-bound
+    /// data_account proves it holds the roles that were not assigned by passing its token account
+    fn check_role(
+        account_metadata: &DataAccountMetadata,
+        role: RoleOption,
+        signer: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        if account_metadata.has_role(role, signer) {
+            return Ok(());
+        }
+
+        match account_metadata.nft_mint() {
+            Some(mint) if account_metadata.role(role) == Some(mint) => {
+                let token_account = Self::find_token_account(accounts, signer, mint)
+                    .ok_or(DataAccountError::InvalidAuthority)?;
+                Self::check_token_account(signer, token_account, mint, 1)
+            }
+            _ => Err(DataAccountError::InvalidAuthority.into()),
+        }
+    }
+
+    /// Ensures the token_account is a token account of the mint owned by the owner and holds at
+    /// least the amount
+    fn check_token_account(
+        owner: &Pubkey,
+        token_account: &AccountInfo,
+        mint: &Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        // ensure token_account is owned by the token program
+        if *token_account.owner != spl_token::id() {
+            return Err(DataAccountError::InvalidTokenAccount.into());
+        }
+
+        let token = spl_token::state::Account::unpack(&token_account.try_borrow_data()?)
+            .map_err(|_| DataAccountError::InvalidTokenAccount)?;
+
+        // ensure token_account belongs to the owner and holds the mint
+        if token.owner != *owner || token.mint != *mint {
+            return Err(DataAccountError::InvalidTokenAccount.into());
+        }
+
+        // ensure token_account holds enough tokens
+        if token.amount < amount {
+            return Err(DataAccountError::InsufficientTokens.into());
+        }
+
+        Ok(())
+    }
+
+    /// Ensures the data fits the data_account without touching frozen data and writes it at
+    /// the offset, reallocing the data_account and filling any gap as needed
+    fn write_data<'a>(
+        payer: &AccountInfo<'a>,
+        refund_account: Option<&AccountInfo<'a>>,
+        data_account: &AccountInfo<'a>,
+        metadata_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        mut account_metadata: DataAccountMetadata,
+        args: UpdateDataAccountArgs,
+    ) -> ProgramResult {
+        let old_len = data_account.data_len();
+        let offset = usize::try_from(args.offset).map_err(|_| DataAccountError::Overflow)?;
+        let end_len = offset
+            .checked_add(args.data.len())
+            .ok_or(DataAccountError::Overflow)?;
+
+        // ensure data_account neither is nor becomes an append-only log
+        if *account_metadata.data_type() == DataTypeOption::LOG
+            || args.data_type == DataTypeOption::LOG
+        {
+            return Err(DataAccountError::AppendOnly.into());
+        }
+
+        // ensure data_account neither is nor becomes a ring buffer
+        if *account_metadata.data_type() == DataTypeOption::RING
+            || args.data_type == DataTypeOption::RING
+        {
+            return Err(DataAccountError::RingBuffer.into());
+        }
+
+        // ensure static data_account has sufficient space
+        if !account_metadata.dynamic() && old_len < end_len {
+            return Err(DataAccountError::InsufficientSpace.into());
+        }
+
+        // ensure dynamic data_account is not left with a gap unless explicitly allowed
+        if offset > old_len && !args.allow_sparse {
+            return Err(DataAccountError::SparseWrite.into());
+        }
+
+        // ensure neither the written data nor the truncated data is frozen
+        if account_metadata.is_frozen(args.offset, end_len as u64)
+            || (account_metadata.dynamic()
+                && args.realloc_down
+                && account_metadata.is_frozen(end_len as u64, old_len as u64))
+        {
+            return Err(DataAccountError::RangeFrozen.into());
+        }
+
+        if args.debug {
+            msg!("account checks passed");
+        }
+
+        let new_len = if !account_metadata.dynamic() {
+            old_len
+        } else if args.realloc_down {
+            end_len
+        } else {
+            old_len.max(end_len)
+        };
+
+        // update the metadata_account
+        account_metadata.set_data_type(args.data_type);
+        Self::store_metadata(&account_metadata, metadata_account)?;
+
+        // ensure data_account has enough space by reallocing if needed
+        if old_len != new_len {
+            Self::resize_data_account(
+                payer,
+                refund_account,
+                data_account,
+                system_program,
+                new_len,
+            )?;
+
+            if args.debug {
+                msg!("realloc-ed {}", new_len);
+            }
+        }
+
+        // fill the gap between the old end and the offset
+        if offset > old_len {
+            data_account.data.borrow_mut()[old_len..offset].fill(args.fill_byte);
+
+            if args.debug {
+                msg!("filled {} bytes with {}", offset - old_len, args.fill_byte);
+            }
+        }
+
+        // update the data_account
+        if args.debug {
+            msg!(
+                "replaced {:?} with {:?}",
+                &args.data,
+                &data_account.data.borrow()[offset..end_len]
+            );
+        }
+
+        data_account.data.borrow_mut()[offset..end_len].copy_from_slice(&args.data);
+
+        // verify that the data conforms to its data_type if requested
+        if args.verify_flag {
+            let serialization_status = verify(
+                &data_account.data.borrow(),
+                account_metadata.data_type().clone(),
+            );
+
+            if args.debug {
+                msg!("serialization status: {:?}", serialization_status);
+            }
+
+            account_metadata.set_serialization_status(serialization_status);
+            Self::store_metadata(&account_metadata, metadata_account)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the data of an ed25519 program instruction verifying a single signature
+    /// of the message by the public key, all held in the instruction itself
+    fn ed25519_data(public_key: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let (public_key_offset, signature_offset, message_offset) = (16u16, 48u16, 112u16);
+        let mut data = vec![1, 0];
+        for offset in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(public_key.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn set_offset(data: &mut [u8], i: usize, offset: u16) {
+        data[2 + 2 * i..4 + 2 * i].copy_from_slice(&offset.to_le_bytes());
+    }
+
+    #[test]
+    fn parses_signer_of_message() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_data(&signer, b"message");
+        assert_eq!(Processor::parse_relay_signer(&data, b"message"), Ok(signer));
+    }
+
+    #[test]
+    fn rejects_other_message() {
+        let data = ed25519_data(&Pubkey::new_unique(), b"message");
+        for message in [&b"massage"[..], b"messag", b"message!", b""] {
+            assert_eq!(
+                Processor::parse_relay_signer(&data, message),
+                Err(DataAccountError::InvalidRelaySignature.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_or_multiple_signatures() {
+        let mut data = ed25519_data(&Pubkey::new_unique(), b"message");
+        for invalid in [&data[..15], &[][..]] {
+            assert_eq!(
+                Processor::parse_relay_signer(invalid, b"message"),
+                Err(DataAccountError::InvalidRelaySignature.into())
+            );
+        }
+
+        data[0] = 2;
+        assert_eq!(
+            Processor::parse_relay_signer(&data, b"message"),
+            Err(DataAccountError::InvalidRelaySignature.into())
+        );
+    }
+
+    #[test]
+    fn rejects_data_in_other_instructions() {
+        // signature, public key and message instruction indices
+        for i in [1, 3, 6] {
+            let mut data = ed25519_data(&Pubkey::new_unique(), b"message");
+            set_offset(&mut data, i, 0);
+            assert_eq!(
+                Processor::parse_relay_signer(&data, b"message"),
+                Err(DataAccountError::InvalidRelaySignature.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_offsets_out_of_bounds() {
+        let data = ed25519_data(&Pubkey::new_unique(), b"message");
+
+        // public key offset, message offset and message size
+        for (i, offset) in [(2, data.len() as u16 - 31), (4, u16::MAX), (5, 8)] {
+            let mut data = data.clone();
+            set_offset(&mut data, i, offset);
+            assert_eq!(
+                Processor::parse_relay_signer(&data, b"message"),
+                Err(DataAccountError::InvalidRelaySignature.into())
+            );
         }
     }
 }
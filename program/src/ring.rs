@@ -0,0 +1,175 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_error::ProgramError;
+
+use crate::error::DataAccountError;
+
+/// Size of the header at the start of the data of a ring buffer
+pub const RING_HEADER_SIZE: usize = 8 + 8 + 8;
+
+/// Header of a data account of type `RING`, which is followed by `capacity` slots of
+/// `record_size` bytes each
+///
+/// `head` is the slot the next record is written to, which overwrites the oldest record once
+/// all `count` slots are in use
+#[derive(PartialEq, Debug, Clone, Copy, BorshSerialize, BorshDeserialize)]
+pub struct RingHeader {
+    pub head: u64,
+    pub record_size: u64,
+    pub count: u64,
+}
+
+impl RingHeader {
+    /// Default constructor
+    pub fn new(record_size: u64) -> Self {
+        RingHeader {
+            head: 0,
+            record_size,
+            count: 0,
+        }
+    }
+    /// Reads the header from the data of a ring buffer and returns it along with the capacity,
+    /// ensuring the header fits the data
+    pub fn unpack(data: &[u8]) -> Result<(Self, u64), ProgramError> {
+        let header = data
+            .get(..RING_HEADER_SIZE)
+            .and_then(|header| RingHeader::try_from_slice(header).ok())
+            .ok_or(DataAccountError::InvalidRingBuffer)?;
+        let capacity = header.capacity(data.len());
+        if capacity == 0 || header.head >= capacity || header.count > capacity {
+            return Err(DataAccountError::InvalidRingBuffer.into());
+        }
+
+        Ok((header, capacity))
+    }
+    /// Get the number of slots of a ring buffer with the data length
+    pub fn capacity(&self, data_len: usize) -> u64 {
+        match self.record_size {
+            0 => 0,
+            record_size => data_len.saturating_sub(RING_HEADER_SIZE) as u64 / record_size,
+        }
+    }
+    /// Get the offset of the slot in the data of the ring buffer
+    pub fn offset(&self, slot: u64) -> Result<usize, ProgramError> {
+        slot.checked_mul(self.record_size)
+            .and_then(|offset| usize::try_from(offset).ok())
+            .and_then(|offset| offset.checked_add(RING_HEADER_SIZE))
+            .ok_or_else(|| DataAccountError::Overflow.into())
+    }
+    /// Advance the head past the record that was just written, counting it until every slot
+    /// is in use
+    pub fn push(&mut self, capacity: u64) {
+        self.head = (self.head + 1) % capacity;
+        self.count = (self.count + 1).min(capacity);
+    }
+}
+
+/// Returns the records of a ring buffer in chronological order, starting from the oldest
+pub struct RingRecords<'a> {
+    data: &'a [u8],
+    header: RingHeader,
+    capacity: u64,
+    position: u64,
+}
+
+impl<'a> RingRecords<'a> {
+    /// Default constructor, which ensures the header fits the data
+    pub fn new(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let (header, capacity) = RingHeader::unpack(data)?;
+
+        Ok(RingRecords {
+            data,
+            header,
+            capacity,
+            position: 0,
+        })
+    }
+    /// Get the header of the ring buffer
+    pub fn header(&self) -> &RingHeader {
+        &self.header
+    }
+}
+
+impl<'a> Iterator for RingRecords<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.header.count {
+            return None;
+        }
+
+        let slot =
+            (self.header.head + self.capacity - self.header.count + self.position) % self.capacity;
+        self.position += 1;
+
+        let offset = RING_HEADER_SIZE + (slot * self.header.record_size) as usize;
+        Some(&self.data[offset..offset + self.header.record_size as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(record_size: u64, capacity: u64) -> Vec<u8> {
+        let mut data = vec![0; RING_HEADER_SIZE + (record_size * capacity) as usize];
+        RingHeader::new(record_size)
+            .serialize(&mut &mut data[..RING_HEADER_SIZE])
+            .unwrap();
+        data
+    }
+
+    fn push(data: &mut [u8], record: &[u8]) {
+        let (mut header, capacity) = RingHeader::unpack(data).unwrap();
+        let offset = header.offset(header.head).unwrap();
+        data[offset..offset + record.len()].copy_from_slice(record);
+        header.push(capacity);
+        header
+            .serialize(&mut &mut data[..RING_HEADER_SIZE])
+            .unwrap();
+    }
+
+    #[test]
+    fn push_wraps_head_and_caps_count() {
+        let mut header = RingHeader::new(4);
+        for (head, count) in [(1, 1), (2, 2), (0, 3), (1, 3), (2, 3)] {
+            header.push(3);
+            assert_eq!((header.head, header.count), (head, count));
+        }
+    }
+
+    #[test]
+    fn unpack_rejects_invalid_headers() {
+        let data = ring(4, 3);
+        assert_eq!(RingHeader::unpack(&data).unwrap().1, 3);
+        assert!(RingHeader::unpack(&data[..RING_HEADER_SIZE - 1]).is_err());
+        assert!(RingHeader::unpack(&data[..RING_HEADER_SIZE + 3]).is_err());
+        assert!(RingHeader::unpack(&ring(0, 3)).is_err());
+
+        let mut data = data;
+        RingHeader {
+            head: 3,
+            record_size: 4,
+            count: 0,
+        }
+        .serialize(&mut &mut data[..RING_HEADER_SIZE])
+        .unwrap();
+        assert!(RingHeader::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn records_are_returned_oldest_first() {
+        let mut data = ring(2, 3);
+        assert_eq!(RingRecords::new(&data).unwrap().count(), 0);
+
+        push(&mut data, b"aa");
+        push(&mut data, b"bb");
+        let records: Vec<&[u8]> = RingRecords::new(&data).unwrap().collect();
+        assert_eq!(records, [b"aa", b"bb"]);
+
+        push(&mut data, b"cc");
+        push(&mut data, b"dd");
+        let records = RingRecords::new(&data).unwrap();
+        assert_eq!(records.header().count, 3);
+        assert_eq!(records.collect::<Vec<_>>(), [b"bb", b"cc", b"dd"]);
+    }
+}
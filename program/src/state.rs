@@ -1,11 +1,41 @@
+use std::io;
+
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde_json::Value;
 use shank::ShankAccount;
-use solana_program::pubkey::Pubkey;
+use solana_program::{hash::hash, pubkey::Pubkey};
 
-pub const DATA_VERSION: u8 = 0;
-pub const METADATA_SIZE: usize = 1 + 1 + 32 + 1 + 1 + 1 + 1;
+pub const DATA_VERSION: u8 = 1;
+pub const METADATA_SIZE: usize =
+    CORE_SIZE + LIFECYCLE_SIZE + FROZEN_RANGES_SIZE + ROLES_SIZE + RECOVERY_SIZE + ACCESS_SIZE;
+/// Sizes of the metadata layouts of the previous data versions, each a prefix of the next one
+pub const LEGACY_METADATA_SIZES: [usize; 1] = [CORE_SIZE];
 pub const PDA_SEED: &[u8] = b"data_account_metadata";
+pub const CONFIG_SIZE: usize = 32 + 1 + 8 + 8 + 8 + 32 + 1 + 8 + 4;
+pub const CONFIG_SEED: &[u8] = b"data_program_config";
+pub const USAGE_SIZE: usize = 32 + 8 + 8 + 8 + 8 + 1;
+pub const USAGE_SEED: &[u8] = b"data_authority_usage";
+pub const SESSION_SIZE: usize = 32 + 32 + 32 + 8 + 8 + 8 + 1 + 1;
+pub const SESSION_SEED: &[u8] = b"data_account_session";
+pub const DELEGATION_SIZE: usize = 32 + 32 + 32 + (8 + 8) * MAX_DELEGATE_RANGES + 1;
+pub const DELEGATION_SEED: &[u8] = b"data_account_delegation";
+pub const DELEGATE_SIGNER_SEED: &[u8] = b"data_program_delegate";
+pub const RELAY_DOMAIN: &[u8] = b"data_program_relayed_update";
+pub const PATH_SEPARATOR: char = '/';
+pub const MAX_ENTRY_NAME_LEN: usize = 64;
+pub const MAX_FROZEN_RANGES: usize = 8;
+pub const ROLE_COUNT: usize = 4;
+pub const MAX_GUARDIANS: usize = 5;
+pub const MAX_DELEGATE_RANGES: usize = 4;
+const CORE_SIZE: usize = 1 + 1 + 32 + 1 + 1 + 1 + 1;
+const LIFECYCLE_SIZE: usize = 8 + 32 + 8 + 8;
+const FROZEN_RANGES_SIZE: usize = (8 + 8) * MAX_FROZEN_RANGES;
+const ROLES_SIZE: usize = 32 * ROLE_COUNT + 1;
+const RECOVERY_SIZE: usize = 32 * MAX_GUARDIANS + 1 + 8 + 32 + 8;
+const ACCESS_SIZE: usize = ARBITER_SIZE + 8 + TOKEN_GATE_SIZE + 1 + LOG_SIZE;
+const ARBITER_SIZE: usize = 32 + 1;
+const TOKEN_GATE_SIZE: usize = 32 + 8;
+const LOG_SIZE: usize = 1 + 8 + 1 + 32;
 
 #[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
 pub enum DataTypeOption {
@@ -13,6 +43,10 @@ pub enum DataTypeOption {
     JSON = 1,
     IMG = 2,
     HTML = 3,
+    DIRECTORY = 4,
+    MANIFEST = 5,
+    LOG = 6,
+    RING = 7,
 }
 
 #[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
@@ -29,6 +63,24 @@ pub enum SerializationStatusOption {
     FAILED,
 }
 
+/// Role that the authority of a data account can hand to another key or renounce
+#[derive(PartialEq, Debug, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub enum RoleOption {
+    WRITE,
+    FINALIZE,
+    CLOSE,
+    MANAGER,
+}
+
+/// Instruction that a session key can be allowed to sign in place of the write authority
+#[derive(PartialEq, Debug, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub enum SessionInstructionOption {
+    UPDATE,
+    RESERVE,
+    RESIZE,
+    APPEND,
+}
+
 /// Verfies that the data conforms to the data_type
 pub fn verify(data: &[u8], data_type: DataTypeOption) -> SerializationStatusOption {
     if data.is_empty() || data_type == DataTypeOption::CUSTOM {
@@ -36,17 +88,77 @@ pub fn verify(data: &[u8], data_type: DataTypeOption) -> SerializationStatusOpti
     }
     match data_type {
         DataTypeOption::JSON => {
-            let deserialized: Result<Value, serde_json::Error> = serde_json::from_slice(&data);
+            let deserialized: Result<Value, serde_json::Error> = serde_json::from_slice(data);
             if deserialized.is_err() {
                 SerializationStatusOption::FAILED
             } else {
                 SerializationStatusOption::VERIFIED
             }
         }
+        DataTypeOption::DIRECTORY => {
+            if Directory::unpack(data).is_err() {
+                SerializationStatusOption::FAILED
+            } else {
+                SerializationStatusOption::VERIFIED
+            }
+        }
+        DataTypeOption::MANIFEST => {
+            if Manifest::unpack(data).is_err() {
+                SerializationStatusOption::FAILED
+            } else {
+                SerializationStatusOption::VERIFIED
+            }
+        }
         _ => SerializationStatusOption::FAILED,
     }
 }
 
+/// Verifies that the name can be used as a directory entry
+pub fn is_valid_entry_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_ENTRY_NAME_LEN
+        && name != "."
+        && name != ".."
+        && !name.contains(PATH_SEPARATOR)
+}
+
+/// Immutable `[start, end)` byte range of a data account, empty if `start == end`
+#[derive(PartialEq, Debug, Clone, Copy, Default, BorshDeserialize, BorshSerialize)]
+pub struct FrozenRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl FrozenRange {
+    /// Check whether the range is empty i.e., an unused slot
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+    /// Check whether the range overlaps the `[start, end)` byte range
+    pub fn overlaps(&self, start: u64, end: u64) -> bool {
+        !self.is_empty() && start < end && self.start < end && start < self.end
+    }
+}
+
+/// `[start, end)` byte range of a data account that an approved program can write to,
+/// empty if `start == end`
+#[derive(PartialEq, Debug, Clone, Copy, Default, BorshDeserialize, BorshSerialize)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Check whether the range is empty i.e., an unused slot
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+    /// Check whether the range contains the `[start, end)` byte range
+    pub fn contains(&self, start: u64, end: u64) -> bool {
+        !self.is_empty() && self.start <= start && end <= self.end
+    }
+}
+
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize, ShankAccount)]
 pub struct DataAccountMetadata {
     data_status: DataStatusOption,
@@ -56,6 +168,31 @@ pub struct DataAccountMetadata {
     data_version: u8,
     data_type: DataTypeOption,
     bump_seed: u8,
+    expires_at_slot: u64,
+    beneficiary: Pubkey,
+    finalize_at: i64,
+    finalize_at_slot: u64,
+    // shank requires a literal length, which must equal MAX_FROZEN_RANGES
+    frozen_ranges: [FrozenRange; 8],
+    // shank requires a literal length, which must equal ROLE_COUNT
+    roles: [Pubkey; 4],
+    renounced_roles: u8,
+    // shank requires a literal length, which must equal MAX_GUARDIANS
+    guardians: [Pubkey; 5],
+    guardian_threshold: u8,
+    recovery_delay: i64,
+    recovery_authority: Pubkey,
+    recover_at: i64,
+    arbiter: Pubkey,
+    is_arbiter_frozen: bool,
+    relay_nonce: u64,
+    token_gate_mint: Pubkey,
+    token_gate_amount: u64,
+    is_nft_bound: bool,
+    is_public_log: bool,
+    append_price: u64,
+    is_price_per_byte: bool,
+    append_treasury: Pubkey,
 }
 
 impl DataAccountMetadata {
@@ -77,6 +214,28 @@ impl DataAccountMetadata {
             data_version,
             data_type,
             bump_seed,
+            expires_at_slot: 0,
+            beneficiary: Pubkey::default(),
+            finalize_at: 0,
+            finalize_at_slot: 0,
+            frozen_ranges: [FrozenRange::default(); MAX_FROZEN_RANGES],
+            roles: [Pubkey::default(); ROLE_COUNT],
+            renounced_roles: 0,
+            guardians: [Pubkey::default(); MAX_GUARDIANS],
+            guardian_threshold: 0,
+            recovery_delay: 0,
+            recovery_authority: Pubkey::default(),
+            recover_at: 0,
+            arbiter: Pubkey::default(),
+            is_arbiter_frozen: false,
+            relay_nonce: 0,
+            token_gate_mint: Pubkey::default(),
+            token_gate_amount: 0,
+            is_nft_bound: false,
+            is_public_log: false,
+            append_price: 0,
+            is_price_per_byte: false,
+            append_treasury: Pubkey::default(),
         }
     }
     /// Get the data_status
@@ -99,18 +258,39 @@ impl DataAccountMetadata {
     pub fn authority(&self) -> &Pubkey {
         &self.authority
     }
-    /// Set the authority
+    /// Set the authority, handing it every role that was held by the old authority and not
+    /// renounced, and unbinding the data account from any nft
     pub fn set_authority(&mut self, authority: Pubkey) {
+        self.release_authority_roles();
         self.authority = authority;
+        self.is_nft_bound = false;
     }
     /// Get the dynamic flag
     pub fn dynamic(&self) -> bool {
         self.is_dynamic
     }
+    /// Set the dynamic flag
+    pub fn set_dynamic(&mut self, is_dynamic: bool) {
+        self.is_dynamic = is_dynamic;
+    }
+    /// Parse the metadata from the metadata pda data, reading the layout of a previous data
+    /// version as if every field it lacks were unset
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        if LEGACY_METADATA_SIZES.contains(&data.len()) {
+            let mut upgraded = [0; METADATA_SIZE];
+            upgraded[..data.len()].copy_from_slice(data);
+            return DataAccountMetadata::try_from_slice(&upgraded);
+        }
+        DataAccountMetadata::try_from_slice(data)
+    }
     /// Get the current data version
     pub fn version(&self) -> u8 {
         self.data_version
     }
+    /// Set the data version to the current one after migrating the layout
+    pub fn set_version(&mut self, data_version: u8) {
+        self.data_version = data_version;
+    }
     /// Get the data_type
     pub fn data_type(&self) -> &DataTypeOption {
         &self.data_type
@@ -123,6 +303,684 @@ impl DataAccountMetadata {
     pub fn bump_seed(&self) -> u8 {
         self.bump_seed
     }
+    /// Get the slot after which anyone can reclaim the data account
+    pub fn expires_at_slot(&self) -> Option<u64> {
+        if self.expires_at_slot == 0 {
+            None
+        } else {
+            Some(self.expires_at_slot)
+        }
+    }
+    /// Set the slot after which anyone can reclaim the data account
+    pub fn set_expires_at_slot(&mut self, expires_at_slot: Option<u64>) {
+        self.expires_at_slot = expires_at_slot.unwrap_or(0);
+    }
+    /// Get the account that receives the lamports of the data account when it is reclaimed,
+    /// which defaults to the authority unless the data account is bound to an nft
+    pub fn beneficiary(&self) -> Option<&Pubkey> {
+        if self.beneficiary != Pubkey::default() {
+            Some(&self.beneficiary)
+        } else if self.is_nft_bound {
+            None
+        } else {
+            Some(&self.authority)
+        }
+    }
+    /// Set the account that receives the lamports of the data account when it is reclaimed
+    pub fn set_beneficiary(&mut self, beneficiary: Option<Pubkey>) {
+        self.beneficiary = beneficiary.unwrap_or_default();
+    }
+    /// Get the unix timestamp after which the data account is finalized
+    pub fn finalize_at(&self) -> Option<i64> {
+        if self.finalize_at == 0 {
+            None
+        } else {
+            Some(self.finalize_at)
+        }
+    }
+    /// Set the unix timestamp after which the data account is finalized
+    pub fn set_finalize_at(&mut self, finalize_at: Option<i64>) {
+        self.finalize_at = finalize_at.unwrap_or(0);
+    }
+    /// Get the slot after which the data account is finalized
+    pub fn finalize_at_slot(&self) -> Option<u64> {
+        if self.finalize_at_slot == 0 {
+            None
+        } else {
+            Some(self.finalize_at_slot)
+        }
+    }
+    /// Set the slot after which the data account is finalized
+    pub fn set_finalize_at_slot(&mut self, finalize_at_slot: Option<u64>) {
+        self.finalize_at_slot = finalize_at_slot.unwrap_or(0);
+    }
+    /// Check whether finalization is scheduled at a unix timestamp or slot
+    pub fn finalize_scheduled(&self) -> bool {
+        self.finalize_at().is_some() || self.finalize_at_slot().is_some()
+    }
+    /// Get the frozen ranges of the data account
+    pub fn frozen_ranges(&self) -> impl Iterator<Item = &FrozenRange> {
+        self.frozen_ranges.iter().filter(|range| !range.is_empty())
+    }
+    /// Add a frozen range, returning false if there is no room left for it
+    pub fn add_frozen_range(&mut self, range: FrozenRange) -> bool {
+        match self.frozen_ranges.iter_mut().find(|slot| slot.is_empty()) {
+            Some(slot) => {
+                *slot = range;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Check whether the `[start, end)` byte range overlaps any frozen range
+    pub fn is_frozen(&self, start: u64, end: u64) -> bool {
+        self.frozen_ranges().any(|range| range.overlaps(start, end))
+    }
+    /// Get the holder of the role, which defaults to the authority, or None if it was renounced
+    pub fn role(&self, role: RoleOption) -> Option<&Pubkey> {
+        let holder = &self.roles[role as usize];
+        if self.renounced(role) {
+            None
+        } else if *holder == Pubkey::default() {
+            Some(&self.authority)
+        } else {
+            Some(holder)
+        }
+    }
+    /// Set the holder of the role or renounce it for good if None
+    pub fn set_role(&mut self, role: RoleOption, holder: Option<Pubkey>) {
+        self.roles[role as usize] = holder.unwrap_or_default();
+        if holder.is_none() {
+            self.renounced_roles |= 1 << role as u8;
+        }
+    }
+    /// Check whether the role was renounced
+    pub fn renounced(&self, role: RoleOption) -> bool {
+        self.renounced_roles & (1 << role as u8) != 0
+    }
+    /// Check whether the key holds the role
+    ///
+    /// The mint of an nft-bound data account never holds a role itself
+    pub fn has_role(&self, role: RoleOption, key: &Pubkey) -> bool {
+        self.role(role) == Some(key) && self.nft_mint() != Some(key)
+    }
+    /// Renounce the authority, every role and the token gate so that the data account can never
+    /// be updated, closed or transferred again
+    pub fn renounce_authority(&mut self) {
+        self.set_authority(Pubkey::default());
+        for role in [
+            RoleOption::WRITE,
+            RoleOption::FINALIZE,
+            RoleOption::CLOSE,
+            RoleOption::MANAGER,
+        ] {
+            self.set_role(role, None);
+        }
+        self.set_expires_at_slot(None);
+        self.set_beneficiary(None);
+        self.set_guardians(&[], 0, 0);
+        self.set_token_gate(None);
+    }
+    /// Get the recovery guardians of the data account
+    pub fn guardians(&self) -> impl Iterator<Item = &Pubkey> {
+        self.guardians
+            .iter()
+            .filter(|guardian| **guardian != Pubkey::default())
+    }
+    /// Check whether the key is a recovery guardian
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardians().any(|guardian| guardian == key)
+    }
+    /// Get the number of guardians required to initiate a recovery
+    pub fn guardian_threshold(&self) -> u8 {
+        self.guardian_threshold
+    }
+    /// Get the number of seconds a recovery has to wait before it can be completed
+    pub fn recovery_delay(&self) -> i64 {
+        self.recovery_delay
+    }
+    /// Set the recovery guardians, threshold and delay, cancelling any pending recovery
+    ///
+    /// `guardians` should hold at most MAX_GUARDIANS keys
+    pub fn set_guardians(&mut self, guardians: &[Pubkey], threshold: u8, delay: i64) {
+        self.guardians = [Pubkey::default(); MAX_GUARDIANS];
+        self.guardians[..guardians.len()].copy_from_slice(guardians);
+        self.guardian_threshold = threshold;
+        self.recovery_delay = delay;
+        self.set_pending_recovery(None);
+    }
+    /// Get the new authority and the unix timestamp after which the pending recovery
+    /// can be completed
+    pub fn pending_recovery(&self) -> Option<(&Pubkey, i64)> {
+        if self.recover_at == 0 {
+            None
+        } else {
+            Some((&self.recovery_authority, self.recover_at))
+        }
+    }
+    /// Set the new authority and the unix timestamp after which the pending recovery
+    /// can be completed
+    pub fn set_pending_recovery(&mut self, recovery: Option<(Pubkey, i64)>) {
+        let (recovery_authority, recover_at) = recovery.unwrap_or_default();
+        self.recovery_authority = recovery_authority;
+        self.recover_at = recover_at;
+    }
+    /// Rotate the authority to the new authority, handing it every role that was held by the
+    /// old authority and not renounced while keeping the roles handed to other keys
+    pub fn recover(&mut self, authority: Pubkey) {
+        self.set_authority(authority);
+        self.set_pending_recovery(None);
+    }
+    /// Get the arbiter that can freeze the data account
+    pub fn arbiter(&self) -> Option<&Pubkey> {
+        if self.arbiter == Pubkey::default() {
+            None
+        } else {
+            Some(&self.arbiter)
+        }
+    }
+    /// Set the arbiter that can freeze the data account
+    pub fn set_arbiter(&mut self, arbiter: Option<Pubkey>) {
+        self.arbiter = arbiter.unwrap_or_default();
+    }
+    /// Get the arbiter frozen flag
+    pub fn arbiter_frozen(&self) -> bool {
+        self.is_arbiter_frozen
+    }
+    /// Set the arbiter frozen flag
+    pub fn set_arbiter_frozen(&mut self, is_arbiter_frozen: bool) {
+        self.is_arbiter_frozen = is_arbiter_frozen;
+    }
+    /// Get the nonce the next relayed update has to be signed with
+    pub fn relay_nonce(&self) -> u64 {
+        self.relay_nonce
+    }
+    /// Set the nonce the next relayed update has to be signed with
+    pub fn set_relay_nonce(&mut self, relay_nonce: u64) {
+        self.relay_nonce = relay_nonce;
+    }
+    /// Get the mint and minimum amount of tokens that grant anyone holding them write access
+    pub fn token_gate(&self) -> Option<(&Pubkey, u64)> {
+        if self.token_gate_mint == Pubkey::default() {
+            None
+        } else {
+            Some((&self.token_gate_mint, self.token_gate_amount))
+        }
+    }
+    /// Set the mint and minimum amount of tokens that grant anyone holding them write access
+    pub fn set_token_gate(&mut self, token_gate: Option<(Pubkey, u64)>) {
+        let (mint, amount) = token_gate.unwrap_or_default();
+        self.token_gate_mint = mint;
+        self.token_gate_amount = amount;
+    }
+    /// Get the mint of the nft whose holder holds the roles the data account has not assigned
+    pub fn nft_mint(&self) -> Option<&Pubkey> {
+        if self.is_nft_bound {
+            Some(&self.authority)
+        } else {
+            None
+        }
+    }
+    /// Bind the data account to the nft of the mint, which replaces the authority and hands
+    /// the roles held by the authority to the holder of the nft, keeping the roles that were
+    /// renounced or handed to other keys
+    pub fn bind_nft(&mut self, mint: Pubkey) {
+        self.release_authority_roles();
+        self.authority = mint;
+        self.is_nft_bound = true;
+    }
+    /// Reset the roles held by the authority itself so that they follow the next authority
+    fn release_authority_roles(&mut self) {
+        let authority = self.authority;
+        for holder in self.roles.iter_mut() {
+            if *holder == authority {
+                *holder = Pubkey::default();
+            }
+        }
+    }
+    /// Check whether anyone can append to the data account if it is a log
+    pub fn public_log(&self) -> bool {
+        self.is_public_log
+    }
+    /// Set whether anyone can append to the data account if it is a log
+    pub fn set_public_log(&mut self, is_public_log: bool) {
+        self.is_public_log = is_public_log;
+    }
+    /// Get the lamports an appender pays per record, or per byte of data if the flag is set
+    pub fn append_price(&self) -> Option<(u64, bool)> {
+        if self.append_price == 0 {
+            None
+        } else {
+            Some((self.append_price, self.is_price_per_byte))
+        }
+    }
+    /// Get the account the append price is paid to
+    pub fn append_treasury(&self) -> Option<&Pubkey> {
+        if self.append_treasury == Pubkey::default() {
+            None
+        } else {
+            Some(&self.append_treasury)
+        }
+    }
+    /// Set the lamports an appender pays per record or per byte of data and the account they
+    /// are paid to
+    pub fn set_append_price(&mut self, append_price: Option<(u64, bool, Pubkey)>) {
+        let (append_price, is_price_per_byte, treasury) = append_price.unwrap_or_default();
+        self.append_price = append_price;
+        self.is_price_per_byte = is_price_per_byte;
+        self.append_treasury = treasury;
+    }
+}
+
+/// Message signed off-chain by the write authority to allow a relayer to submit an update
+///
+/// The data itself is committed to by its SHA-256 hash and the message is only valid for the
+/// current relay nonce of the data account up to and including `expires_at_slot`
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct RelayedUpdateMessage {
+    pub data_account: Pubkey,
+    pub data_type: DataTypeOption,
+    pub data_hash: [u8; 32],
+    pub offset: u64,
+    pub realloc_down: bool,
+    pub verify_flag: bool,
+    pub allow_sparse: bool,
+    pub fill_byte: u8,
+    pub nonce: u64,
+    pub expires_at_slot: u64,
+}
+
+impl RelayedUpdateMessage {
+    /// Default constructor
+    pub fn new(data_account: Pubkey, args: &RelayedUpdateDataAccountArgs) -> Self {
+        RelayedUpdateMessage {
+            data_account,
+            data_type: args.data_type.clone(),
+            data_hash: hash(&args.data).to_bytes(),
+            offset: args.offset,
+            realloc_down: args.realloc_down,
+            verify_flag: args.verify_flag,
+            allow_sparse: args.allow_sparse,
+            fill_byte: args.fill_byte,
+            nonce: args.nonce,
+            expires_at_slot: args.expires_at_slot,
+        }
+    }
+    /// Get the bytes to be signed, which are prefixed by RELAY_DOMAIN
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        Ok([RELAY_DOMAIN, &self.try_to_vec()?].concat())
+    }
+}
+
+/// Program-wide settings stored in the singleton config PDA
+///
+/// A `max_account_size` or `max_write_size` of 0 means unlimited and a `protocol_fee` or
+/// `storage_fee_per_byte` of 0 means no fee is charged. Authorities in `fee_exempt` are not
+/// charged the storage fee
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, ShankAccount)]
+pub struct DataProgramConfig {
+    admin: Pubkey,
+    is_paused: bool,
+    max_account_size: u64,
+    max_write_size: u64,
+    protocol_fee: u64,
+    treasury: Pubkey,
+    bump_seed: u8,
+    storage_fee_per_byte: u64,
+    fee_exempt: Vec<Pubkey>,
+}
+
+impl DataProgramConfig {
+    /// Default constructor
+    pub fn new(admin: Pubkey, bump_seed: u8) -> Self {
+        DataProgramConfig {
+            admin,
+            is_paused: false,
+            max_account_size: 0,
+            max_write_size: 0,
+            protocol_fee: 0,
+            treasury: admin,
+            bump_seed,
+            storage_fee_per_byte: 0,
+            fee_exempt: Vec::new(),
+        }
+    }
+    /// Get the admin
+    pub fn admin(&self) -> &Pubkey {
+        &self.admin
+    }
+    /// Set the admin
+    pub fn set_admin(&mut self, admin: Pubkey) {
+        self.admin = admin;
+    }
+    /// Get the paused flag
+    pub fn paused(&self) -> bool {
+        self.is_paused
+    }
+    /// Set the paused flag
+    pub fn set_paused(&mut self, is_paused: bool) {
+        self.is_paused = is_paused;
+    }
+    /// Get the maximum data account size
+    pub fn max_account_size(&self) -> u64 {
+        self.max_account_size
+    }
+    /// Set the maximum data account size
+    pub fn set_max_account_size(&mut self, max_account_size: u64) {
+        self.max_account_size = max_account_size;
+    }
+    /// Get the maximum number of bytes written per instruction
+    pub fn max_write_size(&self) -> u64 {
+        self.max_write_size
+    }
+    /// Set the maximum number of bytes written per instruction
+    pub fn set_max_write_size(&mut self, max_write_size: u64) {
+        self.max_write_size = max_write_size;
+    }
+    /// Get the protocol fee charged per initialized data account
+    pub fn protocol_fee(&self) -> u64 {
+        self.protocol_fee
+    }
+    /// Set the protocol fee charged per initialized data account
+    pub fn set_protocol_fee(&mut self, protocol_fee: u64) {
+        self.protocol_fee = protocol_fee;
+    }
+    /// Get the treasury
+    pub fn treasury(&self) -> &Pubkey {
+        &self.treasury
+    }
+    /// Set the treasury
+    pub fn set_treasury(&mut self, treasury: Pubkey) {
+        self.treasury = treasury;
+    }
+    /// Get the bump seed
+    pub fn bump_seed(&self) -> u8 {
+        self.bump_seed
+    }
+    /// Get the storage fee charged per byte a data account grows by
+    pub fn storage_fee_per_byte(&self) -> u64 {
+        self.storage_fee_per_byte
+    }
+    /// Set the storage fee charged per byte a data account grows by
+    pub fn set_storage_fee_per_byte(&mut self, storage_fee_per_byte: u64) {
+        self.storage_fee_per_byte = storage_fee_per_byte;
+    }
+    /// Get the authorities exempt from the storage fee
+    pub fn fee_exempt(&self) -> &[Pubkey] {
+        &self.fee_exempt
+    }
+    /// Set the authorities exempt from the storage fee
+    pub fn set_fee_exempt(&mut self, fee_exempt: Vec<Pubkey>) {
+        self.fee_exempt = fee_exempt;
+    }
+    /// Check whether the authority is exempt from the storage fee
+    pub fn is_fee_exempt(&self, authority: &Pubkey) -> bool {
+        self.fee_exempt.contains(authority)
+    }
+}
+
+/// Bytes and data accounts allocated by an authority, stored in its usage PDA
+///
+/// A `max_bytes` or `max_accounts` of 0 means unlimited
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, ShankAccount)]
+pub struct AuthorityUsage {
+    authority: Pubkey,
+    bytes: u64,
+    accounts: u64,
+    max_bytes: u64,
+    max_accounts: u64,
+    bump_seed: u8,
+}
+
+impl AuthorityUsage {
+    /// Default constructor
+    pub fn new(authority: Pubkey, bump_seed: u8) -> Self {
+        AuthorityUsage {
+            authority,
+            bytes: 0,
+            accounts: 0,
+            max_bytes: 0,
+            max_accounts: 0,
+            bump_seed,
+        }
+    }
+    /// Get the authority
+    pub fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+    /// Get the number of bytes allocated
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+    /// Set the number of bytes allocated
+    pub fn set_bytes(&mut self, bytes: u64) {
+        self.bytes = bytes;
+    }
+    /// Get the number of data accounts allocated
+    pub fn accounts(&self) -> u64 {
+        self.accounts
+    }
+    /// Set the number of data accounts allocated
+    pub fn set_accounts(&mut self, accounts: u64) {
+        self.accounts = accounts;
+    }
+    /// Get the maximum number of bytes that can be allocated
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+    /// Set the maximum number of bytes that can be allocated
+    pub fn set_max_bytes(&mut self, max_bytes: u64) {
+        self.max_bytes = max_bytes;
+    }
+    /// Get the maximum number of data accounts that can be allocated
+    pub fn max_accounts(&self) -> u64 {
+        self.max_accounts
+    }
+    /// Set the maximum number of data accounts that can be allocated
+    pub fn set_max_accounts(&mut self, max_accounts: u64) {
+        self.max_accounts = max_accounts;
+    }
+    /// Get the bump seed
+    pub fn bump_seed(&self) -> u8 {
+        self.bump_seed
+    }
+}
+
+/// Session key that can sign a limited set of instructions in place of the write authority
+/// of a data account, stored in its session PDA
+///
+/// `allowed_instructions` is a bitmask of SessionInstructionOption and a `max_bytes` of 0
+/// means unlimited
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, ShankAccount)]
+pub struct SessionKey {
+    data_account: Pubkey,
+    session_key: Pubkey,
+    authority: Pubkey,
+    expires_at_slot: u64,
+    max_bytes: u64,
+    bytes_written: u64,
+    allowed_instructions: u8,
+    bump_seed: u8,
+}
+
+impl SessionKey {
+    /// Default constructor
+    pub fn new(
+        data_account: Pubkey,
+        session_key: Pubkey,
+        authority: Pubkey,
+        expires_at_slot: u64,
+        max_bytes: u64,
+        allowed_instructions: u8,
+        bump_seed: u8,
+    ) -> Self {
+        SessionKey {
+            data_account,
+            session_key,
+            authority,
+            expires_at_slot,
+            max_bytes,
+            bytes_written: 0,
+            allowed_instructions,
+            bump_seed,
+        }
+    }
+    /// Get the data account the session key can write to
+    pub fn data_account(&self) -> &Pubkey {
+        &self.data_account
+    }
+    /// Get the session key
+    pub fn session_key(&self) -> &Pubkey {
+        &self.session_key
+    }
+    /// Get the write authority that registered the session key
+    pub fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+    /// Get the last slot in which the session key can be used
+    pub fn expires_at_slot(&self) -> u64 {
+        self.expires_at_slot
+    }
+    /// Get the maximum number of bytes that can be written
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+    /// Get the number of bytes written
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+    /// Set the number of bytes written
+    pub fn set_bytes_written(&mut self, bytes_written: u64) {
+        self.bytes_written = bytes_written;
+    }
+    /// Check whether the session key can sign the instruction
+    pub fn allows(&self, instruction: SessionInstructionOption) -> bool {
+        self.allowed_instructions & (1 << instruction as u8) != 0
+    }
+    /// Get the bump seed
+    pub fn bump_seed(&self) -> u8 {
+        self.bump_seed
+    }
+}
+
+/// Program approved by the write authority to write to a data account, stored in its
+/// delegation PDA
+///
+/// The program signs with its delegate signer PDA (seeded by DELEGATE_SIGNER_SEED under the
+/// program id) and its writes are limited to the non-empty `ranges` unless all are empty
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, ShankAccount)]
+pub struct Delegation {
+    data_account: Pubkey,
+    program: Pubkey,
+    authority: Pubkey,
+    // shank requires a literal length, which must equal MAX_DELEGATE_RANGES
+    ranges: [ByteRange; 4],
+    bump_seed: u8,
+}
+
+impl Delegation {
+    /// Default constructor
+    ///
+    /// `ranges` should hold at most MAX_DELEGATE_RANGES ranges
+    pub fn new(
+        data_account: Pubkey,
+        program: Pubkey,
+        authority: Pubkey,
+        ranges: &[ByteRange],
+        bump_seed: u8,
+    ) -> Self {
+        let mut delegation = Delegation {
+            data_account,
+            program,
+            authority,
+            ranges: [ByteRange::default(); MAX_DELEGATE_RANGES],
+            bump_seed,
+        };
+        delegation.ranges[..ranges.len()].copy_from_slice(ranges);
+        delegation
+    }
+    /// Get the data account the program can write to
+    pub fn data_account(&self) -> &Pubkey {
+        &self.data_account
+    }
+    /// Get the approved program
+    pub fn program(&self) -> &Pubkey {
+        &self.program
+    }
+    /// Get the write authority that approved the program
+    pub fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+    /// Get the byte ranges the program can write to
+    pub fn ranges(&self) -> impl Iterator<Item = &ByteRange> {
+        self.ranges.iter().filter(|range| !range.is_empty())
+    }
+    /// Check whether the program can write to the `[start, end)` byte range
+    pub fn allows(&self, start: u64, end: u64) -> bool {
+        self.ranges().next().is_none() || self.ranges().any(|range| range.contains(start, end))
+    }
+    /// Get the bump seed
+    pub fn bump_seed(&self) -> u8 {
+        self.bump_seed
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct DirectoryEntry {
+    pub name: String,
+    pub account: Pubkey,
+    pub is_directory: bool,
+}
+
+/// Data stored in a data account of type `DIRECTORY`
+#[derive(PartialEq, Debug, Clone, Default, BorshDeserialize, BorshSerialize)]
+pub struct Directory {
+    pub entries: Vec<DirectoryEntry>,
+}
+
+impl Directory {
+    /// Parse the directory from the data account data, ignoring any trailing bytes
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        if data.is_empty() {
+            return Ok(Directory::default());
+        }
+        Directory::deserialize(&mut &data[..])
+    }
+    /// Get the entry with the given name
+    pub fn entry(&self, name: &str) -> Option<&DirectoryEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+    /// Get the index of the entry with the given name
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.name == name)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct ShardEntry {
+    pub account: Pubkey,
+    pub len: u64,
+    pub hash: [u8; 32],
+}
+
+/// Data stored in a data account of type `MANIFEST` that lists the ordered shards of a large object
+#[derive(PartialEq, Debug, Clone, Default, BorshDeserialize, BorshSerialize)]
+pub struct Manifest {
+    pub shards: Vec<ShardEntry>,
+}
+
+impl Manifest {
+    /// Parse the manifest from the data account data, ignoring any trailing bytes
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        if data.is_empty() {
+            return Ok(Manifest::default());
+        }
+        Manifest::deserialize(&mut &data[..])
+    }
+    /// Get the total length of the object spanning all shards
+    pub fn total_len(&self) -> u64 {
+        self.shards.iter().map(|shard| shard.len).sum()
+    }
 }
 
 #[derive(Clone, BorshSerialize, BorshDeserialize)]
@@ -134,7 +992,7 @@ pub struct InitializeDataAccountArgs {
     pub debug: bool,
 }
 
-#[derive(Clone, BorshSerialize, BorshDeserialize)]
+#[derive(Clone, BorshSerialize)]
 pub struct UpdateDataAccountArgs {
     pub data_type: DataTypeOption,
     pub data: Vec<u8>,
@@ -142,6 +1000,36 @@ pub struct UpdateDataAccountArgs {
     pub realloc_down: bool,
     pub verify_flag: bool,
     pub debug: bool,
+    pub allow_sparse: bool,
+    pub fill_byte: u8,
+}
+
+impl BorshDeserialize for UpdateDataAccountArgs {
+    /// Parse the args, reading the trailing sparse write fields as unset if they are missing
+    /// so that clients built before sparse writes keep working
+    fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+        let data_type = DataTypeOption::deserialize(buf)?;
+        let data = Vec::<u8>::deserialize(buf)?;
+        let offset = u64::deserialize(buf)?;
+        let realloc_down = bool::deserialize(buf)?;
+        let verify_flag = bool::deserialize(buf)?;
+        let debug = bool::deserialize(buf)?;
+        let (allow_sparse, fill_byte) = if buf.is_empty() {
+            (false, 0)
+        } else {
+            (bool::deserialize(buf)?, u8::deserialize(buf)?)
+        };
+        Ok(UpdateDataAccountArgs {
+            data_type,
+            data,
+            offset,
+            realloc_down,
+            verify_flag,
+            debug,
+            allow_sparse,
+            fill_byte,
+        })
+    }
 }
 
 #[derive(Clone, BorshSerialize, BorshDeserialize)]
@@ -158,3 +1046,509 @@ pub struct FinalizeDataAccountArgs {
 pub struct CloseDataAccountArgs {
     pub debug: bool,
 }
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CreateDirectoryEntryArgs {
+    pub name: String,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RenameDirectoryEntryArgs {
+    pub name: String,
+    pub new_name: String,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct MoveDirectoryEntryArgs {
+    pub name: String,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UnlinkDirectoryEntryArgs {
+    pub name: String,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct AppendShardArgs {
+    pub hash: [u8; 32],
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct FinalizeManifestArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ReserveSpaceArgs {
+    pub space: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ResizeDataAccountArgs {
+    pub len: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetDynamicArgs {
+    pub is_dynamic: bool,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct WithdrawExcessLamportsArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct TopUpRentArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeConfigArgs {
+    pub admin: Pubkey,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct UpdateConfigArgs {
+    pub admin: Pubkey,
+    pub is_paused: bool,
+    pub max_account_size: u64,
+    pub max_write_size: u64,
+    pub protocol_fee: u64,
+    pub treasury: Pubkey,
+    pub storage_fee_per_byte: u64,
+    pub fee_exempt: Vec<Pubkey>,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct WithdrawProtocolFeesArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetUsageQuotaArgs {
+    pub max_bytes: u64,
+    pub max_accounts: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetExpiryArgs {
+    pub expires_at_slot: u64,
+    pub beneficiary: Pubkey,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ReclaimExpiredArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ScheduleFinalizeArgs {
+    pub finalize_at: i64,
+    pub finalize_at_slot: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct FinalizeScheduledArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct FreezeRangeArgs {
+    pub start: u64,
+    pub end: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct TransferRoleArgs {
+    pub role: RoleOption,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RenounceRoleArgs {
+    pub role: RoleOption,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RenounceAuthorityArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetGuardiansArgs {
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub delay: i64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitiateRecoveryArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CancelRecoveryArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CompleteRecoveryArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetArbiterArgs {
+    pub arbiter: Pubkey,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct FreezeDataAccountArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ThawDataAccountArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RelayedUpdateDataAccountArgs {
+    pub data_type: DataTypeOption,
+    pub data: Vec<u8>,
+    pub offset: u64,
+    pub realloc_down: bool,
+    pub verify_flag: bool,
+    pub allow_sparse: bool,
+    pub fill_byte: u8,
+    pub nonce: u64,
+    pub expires_at_slot: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct CreateSessionArgs {
+    pub session_key: Pubkey,
+    pub expires_at_slot: u64,
+    pub max_bytes: u64,
+    pub allowed_instructions: u8,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RevokeSessionArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ApproveDelegateArgs {
+    pub program: Pubkey,
+    pub ranges: Vec<ByteRange>,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct RevokeDelegateArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct DelegatedUpdateDataAccountArgs {
+    pub data_type: DataTypeOption,
+    pub data: Vec<u8>,
+    pub offset: u64,
+    pub realloc_down: bool,
+    pub verify_flag: bool,
+    pub allow_sparse: bool,
+    pub fill_byte: u8,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetTokenGateArgs {
+    pub mint: Pubkey,
+    pub min_amount: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct BindToNftArgs {
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetAppendOnlyArgs {
+    pub public_appends: bool,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct AppendRecordArgs {
+    pub data: Vec<u8>,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct SetAppendPriceArgs {
+    pub price: u64,
+    pub per_byte: bool,
+    pub treasury: Pubkey,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct InitializeRingBufferArgs {
+    pub record_size: u64,
+    pub capacity: u64,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct PushRecordArgs {
+    pub data: Vec<u8>,
+    pub debug: bool,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct MigrateMetadataArgs {
+    pub debug: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frozen_range_overlaps_intersecting_ranges() {
+        let range = FrozenRange { start: 4, end: 8 };
+        for (start, end) in [(0, 5), (7, 12), (4, 8), (5, 6), (0, 12)] {
+            assert!(range.overlaps(start, end), "[{}, {})", start, end);
+        }
+        for (start, end) in [(0, 4), (8, 12), (5, 5), (6, 2)] {
+            assert!(!range.overlaps(start, end), "[{}, {})", start, end);
+        }
+    }
+
+    #[test]
+    fn empty_frozen_range_overlaps_nothing() {
+        let range = FrozenRange { start: 4, end: 4 };
+        assert!(range.is_empty());
+        assert!(!range.overlaps(0, 12));
+    }
+
+    #[test]
+    fn byte_range_contains_inner_ranges() {
+        let range = ByteRange { start: 4, end: 8 };
+        for (start, end) in [(4, 8), (5, 7), (4, 4), (8, 8)] {
+            assert!(range.contains(start, end), "[{}, {})", start, end);
+        }
+        for (start, end) in [(3, 8), (4, 9), (0, 12), (8, 12), (0, 4)] {
+            assert!(!range.contains(start, end), "[{}, {})", start, end);
+        }
+    }
+
+    #[test]
+    fn empty_byte_range_contains_nothing() {
+        let range = ByteRange { start: 4, end: 4 };
+        assert!(range.is_empty());
+        assert!(!range.contains(4, 4));
+    }
+
+    #[test]
+    fn verifies_directories_and_manifests() {
+        let directory = Directory {
+            entries: vec![DirectoryEntry {
+                name: "index.html".to_string(),
+                account: Pubkey::new_unique(),
+                is_directory: false,
+            }],
+        };
+        let mut data = directory.try_to_vec().unwrap();
+        data.extend_from_slice(&[0; 4]);
+        assert_eq!(
+            verify(&data, DataTypeOption::DIRECTORY),
+            SerializationStatusOption::VERIFIED
+        );
+        assert_eq!(
+            verify(&data[..data.len() - 5], DataTypeOption::DIRECTORY),
+            SerializationStatusOption::FAILED
+        );
+
+        let manifest = Manifest {
+            shards: vec![ShardEntry {
+                account: Pubkey::new_unique(),
+                len: 3,
+                hash: [1; 32],
+            }],
+        };
+        let data = manifest.try_to_vec().unwrap();
+        assert_eq!(
+            verify(&data, DataTypeOption::MANIFEST),
+            SerializationStatusOption::VERIFIED
+        );
+        assert_eq!(
+            verify(&data[..8], DataTypeOption::MANIFEST),
+            SerializationStatusOption::FAILED
+        );
+    }
+
+    #[test]
+    fn update_args_without_sparse_fields_are_not_sparse() {
+        let args = UpdateDataAccountArgs {
+            data_type: DataTypeOption::CUSTOM,
+            data: vec![1, 2, 3],
+            offset: 4,
+            realloc_down: false,
+            verify_flag: false,
+            debug: true,
+            allow_sparse: true,
+            fill_byte: 7,
+        };
+        let data = args.try_to_vec().unwrap();
+
+        let unpacked = UpdateDataAccountArgs::try_from_slice(&data).unwrap();
+        assert!(unpacked.debug && unpacked.allow_sparse);
+        assert_eq!(unpacked.fill_byte, 7);
+
+        let legacy = UpdateDataAccountArgs::try_from_slice(&data[..data.len() - 2]).unwrap();
+        assert_eq!(legacy.data, vec![1, 2, 3]);
+        assert_eq!(legacy.offset, 4);
+        assert!(legacy.debug && !legacy.allow_sparse);
+        assert_eq!(legacy.fill_byte, 0);
+    }
+
+    #[test]
+    fn unpacks_legacy_metadata_layouts() {
+        let authority = Pubkey::new_unique();
+        let mut metadata = DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::VERIFIED,
+            authority,
+            true,
+            0,
+            DataTypeOption::JSON,
+            254,
+        );
+        metadata.set_expires_at_slot(Some(10));
+        let data = metadata.try_to_vec().unwrap();
+        assert_eq!(data.len(), METADATA_SIZE);
+
+        let legacy = DataAccountMetadata::unpack(&data[..LEGACY_METADATA_SIZES[0]]).unwrap();
+        assert_eq!(*legacy.authority(), authority);
+        assert_eq!(*legacy.data_type(), DataTypeOption::JSON);
+        assert_eq!(legacy.bump_seed(), 254);
+        assert_eq!(legacy.expires_at_slot(), None);
+        assert_eq!(legacy.role(RoleOption::WRITE), Some(&authority));
+
+        let unpacked = DataAccountMetadata::unpack(&data).unwrap();
+        assert_eq!(unpacked.expires_at_slot(), Some(10));
+        assert!(DataAccountMetadata::unpack(&data[..METADATA_SIZE - 1]).is_err());
+    }
+
+    fn metadata(authority: Pubkey) -> DataAccountMetadata {
+        DataAccountMetadata::new(
+            DataStatusOption::INITIALIZED,
+            SerializationStatusOption::UNVERIFIED,
+            authority,
+            false,
+            0,
+            DataTypeOption::CUSTOM,
+            255,
+        )
+    }
+
+    #[test]
+    fn bind_nft_keeps_delegated_roles() {
+        let authority = Pubkey::new_unique();
+        let writer = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let mut metadata = metadata(authority);
+        metadata.set_role(RoleOption::WRITE, Some(writer));
+        metadata.set_role(RoleOption::FINALIZE, Some(authority));
+        metadata.set_role(RoleOption::CLOSE, None);
+
+        metadata.bind_nft(mint);
+        assert_eq!(metadata.nft_mint(), Some(&mint));
+        assert_eq!(metadata.role(RoleOption::WRITE), Some(&writer));
+        assert_eq!(metadata.role(RoleOption::FINALIZE), Some(&mint));
+        assert_eq!(metadata.role(RoleOption::MANAGER), Some(&mint));
+        assert_eq!(metadata.role(RoleOption::CLOSE), None);
+    }
+
+    #[test]
+    fn set_authority_hands_over_roles_held_by_old_authority() {
+        let authority = Pubkey::new_unique();
+        let writer = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+        let mut metadata = metadata(authority);
+        metadata.set_role(RoleOption::WRITE, Some(writer));
+        metadata.set_role(RoleOption::FINALIZE, Some(authority));
+        metadata.set_role(RoleOption::MANAGER, Some(authority));
+        metadata.set_role(RoleOption::CLOSE, None);
+
+        metadata.set_authority(new_authority);
+        assert_eq!(metadata.role(RoleOption::WRITE), Some(&writer));
+        assert_eq!(metadata.role(RoleOption::FINALIZE), Some(&new_authority));
+        assert_eq!(metadata.role(RoleOption::MANAGER), Some(&new_authority));
+        assert_eq!(metadata.role(RoleOption::CLOSE), None);
+        assert!(!metadata.has_role(RoleOption::FINALIZE, &authority));
+    }
+
+    #[test]
+    fn renounce_authority_clears_token_gate() {
+        let mut metadata = metadata(Pubkey::new_unique());
+        metadata.set_token_gate(Some((Pubkey::new_unique(), 1)));
+
+        metadata.renounce_authority();
+        assert_eq!(metadata.token_gate(), None);
+        assert!(metadata.renounced(RoleOption::WRITE));
+    }
+
+    #[test]
+    fn nft_bound_metadata_is_not_reclaimed_to_its_mint() {
+        let authority = Pubkey::new_unique();
+        let mut metadata = metadata(authority);
+        assert_eq!(metadata.beneficiary(), Some(&authority));
+
+        metadata.bind_nft(Pubkey::new_unique());
+        assert_eq!(metadata.beneficiary(), None);
+
+        let beneficiary = Pubkey::new_unique();
+        metadata.set_beneficiary(Some(beneficiary));
+        assert_eq!(metadata.beneficiary(), Some(&beneficiary));
+    }
+
+    #[test]
+    fn append_treasury_does_not_follow_the_authority() {
+        let authority = Pubkey::new_unique();
+        let mut metadata = metadata(authority);
+        metadata.set_append_price(Some((10, true, authority)));
+        assert_eq!(metadata.append_price(), Some((10, true)));
+
+        metadata.set_authority(Pubkey::new_unique());
+        assert_eq!(metadata.append_treasury(), Some(&authority));
+        metadata.bind_nft(Pubkey::new_unique());
+        assert_eq!(metadata.append_treasury(), Some(&authority));
+
+        metadata.set_append_price(None);
+        assert_eq!(metadata.append_price(), None);
+        assert_eq!(metadata.append_treasury(), None);
+    }
+}